@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rlkc::{lexer, parser};
+
+// 임의의 소스 → lex() → parse_program()
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(tokens) = lexer::lex(source) {
+        let _ = parser::Parser::new(tokens).parse_program();
+    }
+});
@@ -0,0 +1,90 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rlkc::lexer::Token;
+use rlkc::parser;
+
+// lexer 를 거치지 않은 임의의 토큰열 (EOF 누락 포함)
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Arbitrary, Debug)]
+enum FuzzToken {
+    Func,
+    Let,
+    Return,
+    If,
+    Else,
+    When,
+    For,
+    In,
+    IntType,
+    StringType,
+    Ident(String),
+    Number(i64),
+    StringLiteral(String),
+    DocComment(String),
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Comma,
+    Colon,
+    Semicolon,
+    Assign,
+    Arrow,
+    DotDot,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Greater,
+    Less,
+    EqualEqual,
+    NotEqual,
+    EOF,
+}
+
+impl From<FuzzToken> for Token {
+    fn from(t: FuzzToken) -> Token {
+        match t {
+            FuzzToken::Func => Token::Func,
+            FuzzToken::Let => Token::Let,
+            FuzzToken::Return => Token::Return,
+            FuzzToken::If => Token::If,
+            FuzzToken::Else => Token::Else,
+            FuzzToken::When => Token::When,
+            FuzzToken::For => Token::For,
+            FuzzToken::In => Token::In,
+            FuzzToken::IntType => Token::IntType,
+            FuzzToken::StringType => Token::StringType,
+            FuzzToken::Ident(s) => Token::Ident(s),
+            FuzzToken::Number(n) => Token::Number(n),
+            FuzzToken::StringLiteral(s) => Token::StringLiteral(s),
+            FuzzToken::DocComment(s) => Token::DocComment(s),
+            FuzzToken::LParen => Token::LParen,
+            FuzzToken::RParen => Token::RParen,
+            FuzzToken::LBrace => Token::LBrace,
+            FuzzToken::RBrace => Token::RBrace,
+            FuzzToken::Comma => Token::Comma,
+            FuzzToken::Colon => Token::Colon,
+            FuzzToken::Semicolon => Token::Semicolon,
+            FuzzToken::Assign => Token::Assign,
+            FuzzToken::Arrow => Token::Arrow,
+            FuzzToken::DotDot => Token::DotDot,
+            FuzzToken::Plus => Token::Plus,
+            FuzzToken::Minus => Token::Minus,
+            FuzzToken::Star => Token::Star,
+            FuzzToken::Slash => Token::Slash,
+            FuzzToken::Greater => Token::Greater,
+            FuzzToken::Less => Token::Less,
+            FuzzToken::EqualEqual => Token::EqualEqual,
+            FuzzToken::NotEqual => Token::NotEqual,
+            FuzzToken::EOF => Token::EOF,
+        }
+    }
+}
+
+fuzz_target!(|tokens: Vec<FuzzToken>| {
+    let tokens: Vec<Token> = tokens.into_iter().map(Token::from).collect();
+    let _ = parser::Parser::new(tokens).parse_program();
+});
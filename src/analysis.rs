@@ -0,0 +1,214 @@
+use crate::semantic::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+// =====================================================
+// CALL GRAPH
+// 함수 → 직접 호출하는 사용자 함수 (runtime / builtin 제외)
+// =====================================================
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallGraph {
+    // 정의 순서
+    pub funcs: Vec<String>,
+    // 호출 순서, 중복 없음
+    pub calls: HashMap<String, Vec<String>>,
+}
+
+impl CallGraph {
+    pub fn build(ir: &IRProgram) -> CallGraph {
+        let mut funcs = Vec::new();
+        let mut calls = HashMap::new();
+
+        for f in &ir.funcs {
+            let mut callees: Vec<String> = Vec::new();
+            for stmt in &f.body {
+                visit_stmt_exprs(stmt, &mut |e| {
+                    if let IRExpr::Call(name, _) = e {
+                        if !callees.contains(name) {
+                            callees.push(name.clone());
+                        }
+                    }
+                });
+            }
+
+            funcs.push(f.name.clone());
+            calls.insert(f.name.clone(), callees);
+        }
+
+        CallGraph { funcs, calls }
+    }
+
+    fn callees(&self, name: &str) -> &[String] {
+        self.calls.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    // root 자신을 포함해 root 에서 호출될 수 있는 모든 함수
+    pub fn reachable_from(&self, root: &str) -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut work = vec![root.to_string()];
+
+        while let Some(name) = work.pop() {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            work.extend(self.callees(&name).iter().cloned());
+        }
+
+        seen
+    }
+
+    // root 에서 절대 호출되지 않는 함수 (정의 순서)
+    pub fn unreachable_from(&self, root: &str) -> Vec<String> {
+        let reachable = self.reachable_from(root);
+        self.funcs
+            .iter()
+            .filter(|f| !reachable.contains(*f))
+            .cloned()
+            .collect()
+    }
+
+    // 자기 자신으로 (직접 또는 다른 함수를 거쳐) 돌아오는 호출이 있는지
+    pub fn is_recursive(&self, name: &str) -> bool {
+        self.callees(name)
+            .iter()
+            .any(|c| self.reachable_from(c).contains(name))
+    }
+
+    // 서로 재귀하는 함수 묶음 (예: [fact], [is_even, is_odd])
+    pub fn recursive_groups(&self) -> Vec<Vec<String>> {
+        let mut groups: Vec<Vec<String>> = Vec::new();
+        let mut grouped = HashSet::new();
+
+        for f in &self.funcs {
+            if grouped.contains(f) || !self.is_recursive(f) {
+                continue;
+            }
+
+            let from_f = self.reachable_from(f);
+            let group: Vec<String> = self
+                .funcs
+                .iter()
+                .filter(|g| from_f.contains(*g) && self.reachable_from(g).contains(f))
+                .cloned()
+                .collect();
+
+            grouped.extend(group.iter().cloned());
+            groups.push(group);
+        }
+
+        groups
+    }
+
+    // --emit=callgraph
+    //   root         → 굵게
+    //   재귀 함수     → 빨강
+    //   도달 불가 함수 → 점선
+    pub fn to_dot(&self, root: &str) -> String {
+        let mut out = String::new();
+        let reachable = self.reachable_from(root);
+
+        writeln!(out, "digraph callgraph {{").unwrap();
+        writeln!(out, "    node [shape=box];").unwrap();
+
+        for f in &self.funcs {
+            let mut attrs = Vec::new();
+            if f == root {
+                attrs.push("style=bold");
+            } else if !reachable.contains(f) {
+                attrs.push("style=dashed");
+            }
+            if self.is_recursive(f) {
+                attrs.push("color=red");
+            }
+
+            if attrs.is_empty() {
+                writeln!(out, "    \"{}\";", f).unwrap();
+            } else {
+                writeln!(out, "    \"{}\" [{}];", f, attrs.join(", ")).unwrap();
+            }
+        }
+
+        for f in &self.funcs {
+            for callee in self.callees(f) {
+                writeln!(out, "    \"{}\" -> \"{}\";", f, callee).unwrap();
+            }
+        }
+
+        writeln!(out, "}}").unwrap();
+        out
+    }
+}
+
+// DCE: root 에서 도달할 수 없는 함수를 제거하고 그 이름을 돌려줌
+pub fn remove_unreachable(ir: &mut IRProgram, root: &str) -> Vec<String> {
+    let removed = CallGraph::build(ir).unreachable_from(root);
+    ir.funcs.retain(|f| !removed.contains(&f.name));
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::compile;
+
+    const SRC: &str = "
+        func fact(n: Int): Int { return when { n < 2 -> 1, else -> n * fact(n - 1) }; }
+        func is_even(n: Int): Int { return when { n == 0 -> 1, else -> is_odd(n - 1) }; }
+        func is_odd(n: Int): Int { return when { n == 0 -> 0, else -> is_even(n - 1) }; }
+        func helper(): Int { return 1; }
+        func unused(): Int { return helper(); }
+        func main(): Int { let x: Int = fact(5) + is_even(4); return x; }
+    ";
+
+    #[test]
+    fn builds_direct_call_edges() {
+        let graph = CallGraph::build(&compile(SRC));
+        assert_eq!(graph.calls["main"], vec!["fact".to_string(), "is_even".to_string()]);
+        assert_eq!(graph.calls["fact"], vec!["fact".to_string()]);
+        assert!(graph.calls["helper"].is_empty());
+    }
+
+    #[test]
+    fn finds_functions_unreachable_from_main() {
+        let graph = CallGraph::build(&compile(SRC));
+        assert_eq!(graph.unreachable_from("main"), vec!["helper".to_string(), "unused".to_string()]);
+    }
+
+    #[test]
+    fn finds_direct_and_mutual_recursion() {
+        let graph = CallGraph::build(&compile(SRC));
+        assert!(graph.is_recursive("fact"));
+        assert!(graph.is_recursive("is_odd"));
+        assert!(!graph.is_recursive("main"));
+        assert!(!graph.is_recursive("unused"));
+
+        assert_eq!(
+            graph.recursive_groups(),
+            vec![
+                vec!["fact".to_string()],
+                vec!["is_even".to_string(), "is_odd".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn dot_marks_root_recursion_and_dead_code() {
+        let dot = CallGraph::build(&compile(SRC)).to_dot("main");
+        assert!(dot.starts_with("digraph callgraph {\n"));
+        assert!(dot.contains("    \"main\" [style=bold];\n"));
+        assert!(dot.contains("    \"fact\" [color=red];\n"));
+        assert!(dot.contains("    \"unused\" [style=dashed];\n"));
+        assert!(dot.contains("    \"is_even\" -> \"is_odd\";\n"));
+        assert!(dot.contains("    \"unused\" -> \"helper\";\n"));
+    }
+
+    #[test]
+    fn removes_unreachable_functions() {
+        let mut ir = compile(SRC);
+        let removed = remove_unreachable(&mut ir, "main");
+        assert_eq!(removed, vec!["helper".to_string(), "unused".to_string()]);
+
+        let names: Vec<&str> = ir.funcs.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["fact", "is_even", "is_odd", "main"]);
+    }
+}
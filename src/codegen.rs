@@ -1,45 +1,198 @@
 use crate::semantic::*;
 use std::fmt::Write;
 
-pub struct Codegen;
+// Bin → ENTRY stub 포함 실행 파일용
+// Lib → ENTRY 없이 함수들을 C 심볼로 export (C / Rust 에서 링크)
+//        main 은 호스트의 main 과 겹치므로 export 하지 않음
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CrateType {
+    #[default]
+    Bin,
+    Lib,
+}
+
+impl CrateType {
+    pub fn from_name(name: &str) -> Option<CrateType> {
+        match name {
+            "bin" => Some(CrateType::Bin),
+            "lib" => Some(CrateType::Lib),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Codegen {
+    pub crate_type: CrateType,
+    pub os: Os,
+}
+
+// 출력 ASM 이 링크될 OS (C 심볼 이름 / 호출 규약)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Os {
+    Linux,
+    MacOs,
+    Windows,
+}
+
+impl Os {
+    pub fn host() -> Os {
+        if cfg!(target_os = "macos") {
+            Os::MacOs
+        } else if cfg!(target_os = "windows") {
+            Os::Windows
+        } else {
+            Os::Linux
+        }
+    }
+
+    // macOS (Mach-O) 는 C 심볼 앞에 '_' — x86_64 / arm64 공통
+    pub fn c_symbol(&self, name: &str) -> String {
+        match self {
+            Os::MacOs => format!("_{}", name),
+            _ => name.to_string(),
+        }
+    }
+
+    // x86_64 C 호출 규약 정수 인자 레지스터 (Windows x64 / System V)
+    pub fn arg_regs_x86(&self) -> [&'static str; 4] {
+        match self {
+            Os::Windows => ["rcx", "rdx", "r8", "r9"],
+            _ => ["rdi", "rsi", "rdx", "rcx"],
+        }
+    }
+}
+
+impl Default for Os {
+    fn default() -> Self {
+        Os::host()
+    }
+}
+
+// 아직 lowering 하지 못하는 IR → 잘못된 ASM 대신 오류
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodegenError {
+    pub message: String,
+}
+
+impl std::fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+pub type CodegenResult<T> = Result<T, CodegenError>;
+
+fn error<T>(message: String) -> CodegenResult<T> {
+    Err(CodegenError { message })
+}
+
+fn unsupported_expr<T>(expr: &IRExpr) -> CodegenResult<T> {
+    match expr {
+        IRExpr::Var(name) => error(format!("reading variable `{}` is not supported by codegen yet", name)),
+        IRExpr::Binary(_, op, _) => error(format!("binary operator `{}` is not supported by codegen yet", op)),
+        IRExpr::When(..) => error("`when` / `if` expressions are not supported by codegen yet".to_string()),
+        other => error(format!("expression {:?} is not supported by codegen yet", other)),
+    }
+}
+
+fn unsupported_stmt<T>(stmt: &IR) -> CodegenResult<T> {
+    match stmt {
+        IR::If(..) => error("`if` statements are not supported by codegen yet".to_string()),
+        IR::While(..) => error("`for` loops are not supported by codegen yet".to_string()),
+        other => error(format!("statement {:?} is not supported by codegen yet", other)),
+    }
+}
+
+// 값은 아직 저장하지 않으므로 let / 식문은 호출이 들어 있을 때만 계산
+fn has_side_effects(expr: &IRExpr) -> bool {
+    let mut found = false;
+    visit_expr(expr, &mut |e| {
+        if matches!(e, IRExpr::Call(..) | IRExpr::CallRuntime(..)) {
+            found = true;
+        }
+    });
+    found
+}
+
+// 오류 메시지에 함수 이름을 붙임
+fn in_function<T>(f: &IRFunction, result: CodegenResult<T>) -> CodegenResult<T> {
+    result.map_err(|e| CodegenError {
+        message: format!("in `{}`: {}", f.name, e.message),
+    })
+}
 
 // 공통 ENTRY POINT = main
 const ENTRY: &str = "main";
 
-// =====================================================
-// 아키텍처 자동 감지
-// =====================================================
-fn detect_arch() -> &'static str {
-    if cfg!(target_arch = "aarch64") {
-        "arm64"
-    } else {
-        "x86_64"
+// 모든 ret 앞에서 frame / x30 복원
+const ARM64_EPILOGUE: &str = "    ldp x29, x30, [sp], 16\n";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    X86_64,
+    Arm64,
+}
+
+impl Target {
+    // =====================================================
+    // 아키텍처 자동 감지
+    // =====================================================
+    pub fn host() -> Target {
+        if cfg!(target_arch = "aarch64") {
+            Target::Arm64
+        } else {
+            Target::X86_64
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Target> {
+        match name {
+            "x86_64" => Some(Target::X86_64),
+            "arm64" | "aarch64" => Some(Target::Arm64),
+            _ => None,
+        }
+    }
+
+    // 출력 파일 확장자 (NASM / GNU as)
+    pub fn asm_extension(&self) -> &'static str {
+        match self {
+            Target::X86_64 => "asm",
+            Target::Arm64 => "s",
+        }
     }
 }
 
 impl Codegen {
+    // lib 에서 C 심볼로 내보낼 함수
+    fn exports(&self, f: &IRFunction) -> bool {
+        self.crate_type == CrateType::Lib && f.name != ENTRY
+    }
+
     // =====================================================
     // generate() → 아키텍처 분기
     // =====================================================
-    pub fn generate(&self, ir: &IRProgram) -> String {
-        let arch = detect_arch();
+    pub fn generate(&self, ir: &IRProgram) -> CodegenResult<String> {
+        self.generate_for(ir, Target::host())
+    }
 
-        if arch == "arm64" {
-            self.generate_arm64(ir)
-        } else {
-            self.generate_x86_64(ir)
+    pub fn generate_for(&self, ir: &IRProgram, target: Target) -> CodegenResult<String> {
+        match target {
+            Target::Arm64 => self.generate_arm64(ir),
+            Target::X86_64 => self.generate_x86_64(ir),
         }
     }
 
     // =====================================================
     // X86_64 BACKEND (네 기존 코드 그대로)
     // =====================================================
-    pub fn generate_x86_64(&self, ir: &IRProgram) -> String {
+    pub fn generate_x86_64(&self, ir: &IRProgram) -> CodegenResult<String> {
         let mut out = String::new();
 
         // DATA
         writeln!(&mut out, "section .data").unwrap();
         writeln!(&mut out, "fmt_str: db \"%s\", 0").unwrap();
+        writeln!(&mut out, "fmt_ln: db \"%s\", 10, 0").unwrap();
 
         let mut strs = Vec::new();
         for f in &ir.funcs {
@@ -54,61 +207,86 @@ impl Codegen {
 
         // TEXT
         writeln!(&mut out, "section .text").unwrap();
-        writeln!(&mut out, "global {}", ENTRY).unwrap();
+        if self.crate_type == CrateType::Bin {
+            writeln!(&mut out, "global {}", self.os.c_symbol(ENTRY)).unwrap();
+        }
 
-        #[cfg(target_os = "macos")]
-        writeln!(&mut out, "extern _printf").unwrap();
+        writeln!(&mut out, "extern {}", self.os.c_symbol("printf")).unwrap();
 
-        #[cfg(not(target_os = "macos"))]
-        writeln!(&mut out, "extern printf").unwrap();
+        for symbol in self.collect_runtime(ir) {
+            writeln!(&mut out, "extern {}", self.os.c_symbol(&symbol)).unwrap();
+        }
 
         for f in &ir.funcs {
-            writeln!(&mut out, "global {}_func", f.name).unwrap();
-            writeln!(&mut out, "global {}_func_end", f.name).unwrap();
+            match self.crate_type {
+                CrateType::Bin => {
+                    writeln!(&mut out, "global {}_func", f.name).unwrap();
+                    writeln!(&mut out, "global {}_func_end", f.name).unwrap();
+                }
+                CrateType::Lib if self.exports(f) => {
+                    writeln!(&mut out, "global {}", self.os.c_symbol(&f.name)).unwrap();
+                }
+                CrateType::Lib => {}
+            }
         }
 
         for f in &ir.funcs {
-            self.gen_function_x86(&mut out, f, &strs);
+            in_function(f, self.gen_function_x86(&mut out, f, &strs))?;
         }
 
         // ENTRY main()
-        writeln!(&mut out, "{}:", ENTRY).unwrap();
-        writeln!(&mut out, "    call main_func").unwrap();
-        writeln!(&mut out, "    mov eax, 0").unwrap();
-        writeln!(&mut out, "    ret").unwrap();
+        if self.crate_type == CrateType::Bin {
+            writeln!(&mut out, "{}:", self.os.c_symbol(ENTRY)).unwrap();
+            writeln!(&mut out, "    call main_func").unwrap();
+            writeln!(&mut out, "    mov eax, 0").unwrap();
+            writeln!(&mut out, "    ret").unwrap();
+        }
 
-        out
+        Ok(out)
     }
 
-    fn gen_function_x86(&self, out: &mut String, f: &IRFunction, strs: &Vec<String>) {
+    fn gen_function_x86(&self, out: &mut String, f: &IRFunction, strs: &[String]) -> CodegenResult<()> {
+        // lib: C 에서 부르는 이름 = 함수 이름
+        if self.exports(f) {
+            writeln!(out, "{}:", self.os.c_symbol(&f.name)).unwrap();
+        }
         writeln!(out, "{}_func:", f.name).unwrap();
         for stmt in &f.body {
-            self.gen_stmt_x86(out, stmt, strs);
+            self.gen_stmt_x86(out, stmt, strs)?;
         }
         writeln!(out, "{}_func_end:", f.name).unwrap();
         writeln!(out, "    ret").unwrap();
+        Ok(())
     }
 
-    fn gen_stmt_x86(&self, out: &mut String, stmt: &IR, strs: &Vec<String>) {
+    fn gen_stmt_x86(&self, out: &mut String, stmt: &IR, strs: &[String]) -> CodegenResult<()> {
         match stmt {
             IR::Return(expr) => {
-                self.gen_expr_x86(out, expr, strs);
+                self.gen_expr_x86(out, expr, strs)?;
                 writeln!(out, "    ret").unwrap();
             }
 
+            IR::Print(expr) => {
+                self.gen_print_x86(out, "fmt_str", expr, strs)?;
+            }
+
             IR::Println(expr) => {
-                self.gen_print_x86(out, expr, strs);
+                self.gen_print_x86(out, "fmt_ln", expr, strs)?;
             }
 
-            IR::StoreVar(_, expr) => {
-                self.gen_expr_x86(out, expr, strs);
+            IR::StoreVar(_, expr) if has_side_effects(expr) => {
+                self.gen_expr_x86(out, expr, strs)?;
             }
 
-            _ => {}
+            IR::StoreVar(..) => {}
+
+            // 분기 / 반복 (compare-and-branch) 은 아직 없음
+            _ => return unsupported_stmt(stmt),
         }
+        Ok(())
     }
 
-    fn gen_expr_x86(&self, out: &mut String, expr: &IRExpr, strs: &Vec<String>) {
+    fn gen_expr_x86(&self, out: &mut String, expr: &IRExpr, strs: &[String]) -> CodegenResult<()> {
         match expr {
             IRExpr::Int(n) => writeln!(out, "    mov rax, {}", n).unwrap(),
 
@@ -117,61 +295,117 @@ impl Codegen {
                 writeln!(out, "    lea rax, [rel str_{}]", idx).unwrap();
             }
 
-            _ => {}
+            IRExpr::CallRuntime(symbol, args) => {
+                self.gen_runtime_call_x86(out, symbol, args, strs)?;
+            }
+
+            IRExpr::Call(name, args) => {
+                self.gen_args_x86(out, name, args, strs)?;
+                writeln!(out, "    call {}_func", name).unwrap();
+            }
+
+            // 변수 (스택 슬롯) / 연산 / 분기는 아직 없음
+            IRExpr::Var(_) | IRExpr::Binary(..) | IRExpr::When(..) => return unsupported_expr(expr),
         }
+        Ok(())
     }
 
-    fn gen_print_x86(&self, out: &mut String, expr: &IRExpr, strs: &Vec<String>) {
-        let idx = if let IRExpr::Str(s) = expr {
-            strs.iter().position(|x| x == s).unwrap()
-        } else {
-            panic!("println only supports string literal");
-        };
+    // 인자를 하나씩 rax 로 계산해 push → 역순으로 인자 레지스터에 pop
+    fn gen_args_x86(&self, out: &mut String, callee: &str, args: &[IRExpr], strs: &[String]) -> CodegenResult<()> {
+        let regs = self.os.arg_regs_x86();
+        if args.len() > regs.len() {
+            return error(format!("call to {} has too many arguments", callee));
+        }
 
-        #[cfg(target_os = "macos")]
-        {
-            writeln!(out, "    lea rdi, [rel fmt_str]").unwrap();
-            writeln!(out, "    lea rsi, [rel str_{}]", idx).unwrap();
-            writeln!(out, "    sub rsp, 32").unwrap();
-            writeln!(out, "    call _printf").unwrap();
-            writeln!(out, "    add rsp, 32").unwrap();
-            return;
+        for a in args {
+            self.gen_expr_x86(out, a, strs)?;
+            writeln!(out, "    push rax").unwrap();
         }
+        for reg in regs[..args.len()].iter().rev() {
+            writeln!(out, "    pop {}", reg).unwrap();
+        }
+        Ok(())
+    }
+
+    // 결과는 rax
+    fn gen_runtime_call_x86(&self, out: &mut String, symbol: &str, args: &[IRExpr], strs: &[String]) -> CodegenResult<()> {
+        self.gen_args_x86(out, symbol, args, strs)?;
 
-        #[cfg(not(target_os = "macos"))]
-        {
-            writeln!(out, "    lea rcx, [rel fmt_str]").unwrap();
-            writeln!(out, "    lea rdx, [rel str_{}]", idx).unwrap();
-            writeln!(out, "    sub rsp, 32").unwrap();
-            writeln!(out, "    call printf").unwrap();
-            writeln!(out, "    add rsp, 32").unwrap();
+        writeln!(out, "    sub rsp, 32").unwrap();
+        writeln!(out, "    call {}", self.os.c_symbol(symbol)).unwrap();
+        writeln!(out, "    add rsp, 32").unwrap();
+        Ok(())
+    }
+
+    // fmt = "fmt_str" (print) | "fmt_ln" (println)
+    fn gen_print_x86(&self, out: &mut String, fmt: &str, expr: &IRExpr, strs: &[String]) -> CodegenResult<()> {
+        // 문자열 리터럴은 바로 lea, 그 외 (runtime 이 만든 heap 문자열 등) 는 rax 로 계산
+        let idx = match expr {
+            IRExpr::Str(s) => Some(strs.iter().position(|x| x == s).unwrap()),
+            _ => {
+                self.gen_expr_x86(out, expr, strs)?;
+                None
+            }
+        };
+
+        // C 호출 규약: 첫 인자 = fmt, 둘째 = 문자열
+        let [fmt_reg, arg_reg, ..] = self.os.arg_regs_x86();
+        match idx {
+            Some(i) => writeln!(out, "    lea {}, [rel str_{}]", arg_reg, i).unwrap(),
+            None => writeln!(out, "    mov {}, rax", arg_reg).unwrap(),
         }
+        writeln!(out, "    lea {}, [rel {}]", fmt_reg, fmt).unwrap();
+
+        // 가변 인자 함수: al = 사용한 벡터 레지스터 수 (0)
+        writeln!(out, "    xor eax, eax").unwrap();
+        writeln!(out, "    sub rsp, 32").unwrap();
+        writeln!(out, "    call {}", self.os.c_symbol("printf")).unwrap();
+        writeln!(out, "    add rsp, 32").unwrap();
+        Ok(())
     }
 
-    // X86 string collector
+    // X86 / ARM64 공통 string collector
     fn collect_str(&self, stmt: &IR, out: &mut Vec<String>) {
-        if let IR::Println(IRExpr::Str(s)) = stmt {
-            out.push(s.clone());
+        visit_stmt_exprs(stmt, &mut |e| {
+            if let IRExpr::Str(s) = e {
+                out.push(s.clone());
+            }
+        });
+    }
+
+    // 사용된 runtime 심볼 (중복 없이)
+    fn collect_runtime(&self, ir: &IRProgram) -> Vec<String> {
+        let mut symbols: Vec<String> = Vec::new();
+        for f in &ir.funcs {
+            for stmt in &f.body {
+                visit_stmt_exprs(stmt, &mut |e| {
+                    if let IRExpr::CallRuntime(symbol, _) = e {
+                        if !symbols.contains(symbol) {
+                            symbols.push(symbol.clone());
+                        }
+                    }
+                });
+            }
         }
+        symbols
     }
 
     // =====================================================
     // ARM64 BACKEND (완전한 printf 기반)
     // macOS ARM64 + Linux ARM64 둘 다 동작
     // =====================================================
-    pub fn generate_arm64(&self, ir: &IRProgram) -> String {
+    pub fn generate_arm64(&self, ir: &IRProgram) -> CodegenResult<String> {
         let mut out = String::new();
 
         // DATA
         out.push_str(".data\n");
         out.push_str("fmt_str:\n    .asciz \"%s\"\n");
+        out.push_str("fmt_ln:\n    .asciz \"%s\\n\"\n");
 
         let mut strs = Vec::new();
         for f in &ir.funcs {
             for stmt in &f.body {
-                if let IR::Println(IRExpr::Str(s)) = stmt {
-                    strs.push(s.clone());
-                }
+                self.collect_str(stmt, &mut strs);
             }
         }
 
@@ -181,67 +415,443 @@ impl Codegen {
 
         // TEXT
         out.push_str(".text\n");
-        out.push_str(".global _main\n");
 
-        // ENTRY main()
-        out.push_str("_main:\n");
-        out.push_str("    stp x29, x30, [sp, -16]!\n");
-        out.push_str("    mov x29, sp\n");
-        out.push_str("    bl main_func\n");
-        out.push_str("    mov w0, 0\n");
-        out.push_str("    ldp x29, x30, [sp], 16\n");
-        out.push_str("    ret\n\n");
+        match self.crate_type {
+            CrateType::Bin => {
+                writeln!(out, ".global {}", self.os.c_symbol(ENTRY)).unwrap();
+
+                // ENTRY main()
+                writeln!(out, "{}:", self.os.c_symbol(ENTRY)).unwrap();
+                out.push_str("    stp x29, x30, [sp, -16]!\n");
+                out.push_str("    mov x29, sp\n");
+                out.push_str("    bl main_func\n");
+                out.push_str("    mov w0, 0\n");
+                out.push_str("    ldp x29, x30, [sp], 16\n");
+                out.push_str("    ret\n\n");
+            }
+            CrateType::Lib => {
+                for f in ir.funcs.iter().filter(|f| self.exports(f)) {
+                    writeln!(out, ".global {}", self.os.c_symbol(&f.name)).unwrap();
+                }
+                out.push('\n');
+            }
+        }
 
         // FUNCTIONS
         for f in &ir.funcs {
+            // lib: C 에서 부르는 이름 = 함수 이름
+            if self.exports(f) {
+                writeln!(out, "{}:", self.os.c_symbol(&f.name)).unwrap();
+            }
             writeln!(out, "{}_func:", f.name).unwrap();
+            // bl 이 x30 (복귀 주소) 을 덮어쓰므로 frame 에 저장
+            out.push_str("    stp x29, x30, [sp, -16]!\n");
+            out.push_str("    mov x29, sp\n");
             for stmt in &f.body {
-                self.gen_stmt_arm64(&mut out, stmt, &strs);
+                in_function(f, self.gen_stmt_arm64(&mut out, stmt, &strs))?;
             }
             writeln!(out, "{}_func_end:", f.name).unwrap();
+            out.push_str(ARM64_EPILOGUE);
             out.push_str("    ret\n\n");
         }
 
-        out
+        Ok(out)
     }
 
-    fn gen_stmt_arm64(&self, out: &mut String, stmt: &IR, strs: &Vec<String>) {
+    fn gen_stmt_arm64(&self, out: &mut String, stmt: &IR, strs: &[String]) -> CodegenResult<()> {
         match stmt {
             IR::Return(expr) => {
-                self.gen_expr_arm64(out, expr, strs);
+                self.gen_expr_arm64(out, expr, strs)?;
+                out.push_str(ARM64_EPILOGUE);
                 out.push_str("    ret\n");
             }
+            IR::Print(expr) => {
+                self.gen_print_arm64(out, "fmt_str", expr, strs)?;
+            }
             IR::Println(expr) => {
-                self.gen_print_arm64(out, expr, strs);
+                self.gen_print_arm64(out, "fmt_ln", expr, strs)?;
             }
-            _ => {}
+            // sleep(ms); 처럼 부수 효과만 있는 식문
+            IR::StoreVar(_, expr) if has_side_effects(expr) => {
+                self.gen_expr_arm64(out, expr, strs)?;
+            }
+            IR::StoreVar(..) => {}
+            // 분기 / 반복 (compare-and-branch) 은 아직 없음
+            _ => return unsupported_stmt(stmt),
         }
+        Ok(())
     }
 
-    fn gen_expr_arm64(&self, out: &mut String, expr: &IRExpr, strs: &Vec<String>) {
-        if let IRExpr::Str(s) = expr {
-            let idx = strs.iter().position(|x| x == s).unwrap();
-            writeln!(out, "    adrp x0, str_{}@PAGE", idx).unwrap();
-            writeln!(out, "    add  x0, x0, str_{}@PAGEOFF", idx).unwrap();
+    fn gen_expr_arm64(&self, out: &mut String, expr: &IRExpr, strs: &[String]) -> CodegenResult<()> {
+        match expr {
+            IRExpr::Int(n) => writeln!(out, "    ldr  x0, ={}", n).unwrap(),
+
+            IRExpr::Str(s) => {
+                let idx = strs.iter().position(|x| x == s).unwrap();
+                writeln!(out, "    adrp x0, str_{}@PAGE", idx).unwrap();
+                writeln!(out, "    add  x0, x0, str_{}@PAGEOFF", idx).unwrap();
+            }
+
+            IRExpr::CallRuntime(symbol, args) => {
+                self.gen_runtime_call_arm64(out, symbol, args, strs)?;
+            }
+
+            IRExpr::Call(name, args) => {
+                self.gen_args_arm64(out, name, args, strs)?;
+                writeln!(out, "    bl {}_func", name).unwrap();
+            }
+
+            // 변수 (스택 슬롯) / 연산 / 분기는 아직 없음
+            IRExpr::Var(_) | IRExpr::Binary(..) | IRExpr::When(..) => return unsupported_expr(expr),
         }
+        Ok(())
     }
 
-    fn gen_print_arm64(&self, out: &mut String, expr: &IRExpr, strs: &Vec<String>) {
-        let idx = if let IRExpr::Str(s) = expr {
-            strs.iter().position(|x| x == s).unwrap()
-        } else {
-            panic!("println only supports string literal");
-        };
+    // 인자를 하나씩 x0 로 계산해 스택에 저장 → 역순으로 x0..x7 에 복원
+    fn gen_args_arm64(&self, out: &mut String, callee: &str, args: &[IRExpr], strs: &[String]) -> CodegenResult<()> {
+        if args.len() > 8 {
+            return error(format!("call to {} has too many arguments", callee));
+        }
+
+        for a in args {
+            self.gen_expr_arm64(out, a, strs)?;
+            out.push_str("    str  x0, [sp, -16]!\n");
+        }
+        for i in (0..args.len()).rev() {
+            writeln!(out, "    ldr  x{}, [sp], 16", i).unwrap();
+        }
+        Ok(())
+    }
+
+    // 결과는 x0
+    fn gen_runtime_call_arm64(&self, out: &mut String, symbol: &str, args: &[IRExpr], strs: &[String]) -> CodegenResult<()> {
+        self.gen_args_arm64(out, symbol, args, strs)?;
+        writeln!(out, "    bl {}", self.os.c_symbol(symbol)).unwrap();
+        Ok(())
+    }
 
-        // x0 = fmt_str
-        out.push_str("    adrp x0, fmt_str@PAGE\n");
-        out.push_str("    add  x0, x0, fmt_str@PAGEOFF\n");
+    // fmt = "fmt_str" (print) | "fmt_ln" (println)
+    fn gen_print_arm64(&self, out: &mut String, fmt: &str, expr: &IRExpr, strs: &[String]) -> CodegenResult<()> {
+        // x1 = 출력할 문자열
+        match expr {
+            IRExpr::Str(s) => {
+                let idx = strs.iter().position(|x| x == s).unwrap();
+                writeln!(out, "    adrp x1, str_{}@PAGE", idx).unwrap();
+                writeln!(out, "    add  x1, x1, str_{}@PAGEOFF", idx).unwrap();
+            }
+            _ => {
+                self.gen_expr_arm64(out, expr, strs)?;
+                out.push_str("    mov  x1, x0\n");
+            }
+        }
 
-        // x1 = str_x
-        writeln!(out, "    adrp x1, str_{}@PAGE", idx).unwrap();
-        writeln!(out, "    add  x1, x1, str_{}@PAGEOFF", idx).unwrap();
+        // x0 = fmt
+        writeln!(out, "    adrp x0, {}@PAGE", fmt).unwrap();
+        writeln!(out, "    add  x0, x0, {}@PAGEOFF", fmt).unwrap();
 
         // printf
-        out.push_str("    bl _printf\n");
+        writeln!(out, "    bl {}", self.os.c_symbol("printf")).unwrap();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::compile;
+
+    // 테스트는 호스트 OS 기준
+    fn c_symbol(name: &str) -> String {
+        Os::host().c_symbol(name)
+    }
+
+    fn arg_regs() -> [&'static str; 4] {
+        Os::host().arg_regs_x86()
+    }
+
+    const SRC: &str = "func main(): Int { print(\"a\"); println(\"b\"); return 0; }";
+
+    #[test]
+    fn print_and_println_lower_to_separate_ir() {
+        let ir = compile(SRC);
+        assert!(matches!(ir.funcs[0].body[0], IR::Print(IRExpr::Str(_))));
+        assert!(matches!(ir.funcs[0].body[1], IR::Println(IRExpr::Str(_))));
+    }
+
+    #[test]
+    fn x86_64_uses_newline_format_only_for_println() {
+        let asm = Codegen::default().generate_x86_64(&compile(SRC)).unwrap();
+        assert!(asm.contains("fmt_str: db \"%s\", 0"));
+        assert!(asm.contains("fmt_ln: db \"%s\", 10, 0"));
+
+        let print_at = asm.find("[rel fmt_str]").unwrap();
+        let println_at = asm.find("[rel fmt_ln]").unwrap();
+        assert!(print_at < println_at);
+    }
+
+    #[test]
+    fn x86_64_printf_uses_c_argument_registers() {
+        let asm = Codegen::default().generate_x86_64(&compile(SRC)).unwrap();
+        assert!(asm.contains(&format!(
+            "    lea {}, [rel str_0]\n    lea {}, [rel fmt_str]\n    xor eax, eax\n",
+            arg_regs()[1], arg_regs()[0]
+        )));
+        assert!(asm.contains(&format!("    call {}\n", c_symbol("printf"))));
+
+        // runtime 호출 결과도 같은 레지스터로
+        let asm = Codegen::default().generate_x86_64(&compile(SUBSTRING_SRC)).unwrap();
+        assert!(asm.contains(&format!(
+            "    call {}\n    add rsp, 32\n    mov {}, rax\n",
+            c_symbol("rlk_substring"),
+            arg_regs()[1]
+        )));
+    }
+
+    #[test]
+    fn arm64_uses_newline_format_only_for_println() {
+        let asm = Codegen::default().generate_arm64(&compile(SRC)).unwrap();
+        assert!(asm.contains("fmt_ln:\n    .asciz \"%s\\n\""));
+
+        let print_at = asm.find("adrp x0, fmt_str@PAGE").unwrap();
+        let println_at = asm.find("adrp x0, fmt_ln@PAGE").unwrap();
+        assert!(print_at < println_at);
+    }
+
+    const SUBSTRING_SRC: &str = "func main(): Int { println(substring(\"hello\", 1, 3)); return 0; }";
+
+    #[test]
+    fn x86_64_calls_runtime_substring() {
+        let asm = Codegen::default().generate_x86_64(&compile(SUBSTRING_SRC)).unwrap();
+        let symbol = c_symbol("rlk_substring");
+        assert!(asm.contains(&format!("extern {}", symbol)));
+        assert!(asm.contains(&format!("call {}", symbol)));
+        assert!(asm.contains("str_0: db \"hello\", 0"));
+
+        // 세 인자 모두 스택을 거쳐 인자 레지스터로
+        assert_eq!(asm.matches("    push rax").count(), 3);
+        assert!(asm.contains(&format!("    pop {}", arg_regs()[0])));
+    }
+
+    #[test]
+    fn arm64_calls_runtime_substring() {
+        let asm = Codegen::default().generate_arm64(&compile(SUBSTRING_SRC)).unwrap();
+        assert!(asm.contains(&format!("bl {}", c_symbol("rlk_substring"))));
+        assert!(asm.contains("    ldr  x2, [sp], 16"));
+        assert!(asm.contains("    mov  x1, x0"));
+    }
+
+    const LIB_SRC: &str = "func answer(): Int { return 42; } func greet(): Int { println(\"hi\"); return 0; }";
+
+    fn lib_codegen() -> Codegen {
+        Codegen {
+            crate_type: CrateType::Lib,
+            ..Codegen::default()
+        }
+    }
+
+    #[test]
+    fn x86_64_lib_exports_c_symbols_without_entry() {
+        let asm = lib_codegen().generate_x86_64(&compile(LIB_SRC)).unwrap();
+        let answer = c_symbol("answer");
+
+        assert!(asm.contains(&format!("global {}\n", answer)));
+        assert!(asm.contains(&format!("{}:\nanswer_func:", answer)));
+        assert!(asm.contains(&format!("global {}\n", c_symbol("greet"))));
+
+        assert!(!asm.contains("call main_func"));
+        assert!(!asm.contains(&format!("global {}\n", ENTRY)));
+        assert!(!asm.contains("global answer_func"));
+    }
+
+    #[test]
+    fn arm64_lib_exports_c_symbols_without_entry() {
+        let asm = lib_codegen().generate_arm64(&compile(LIB_SRC)).unwrap();
+        let answer = c_symbol("answer");
+
+        assert!(asm.contains(&format!(".global {}\n", answer)));
+        assert!(asm.contains(&format!("\n{}:\nanswer_func:", answer)));
+        assert!(!asm.contains(&format!(".global {}\n", c_symbol(ENTRY))));
+        assert!(!asm.contains("bl main_func"));
+    }
+
+    #[test]
+    fn arm64_functions_save_link_register() {
+        let src = "func one(): Int { return 1; } func main(): Int { println(\"x\"); return one(); }";
+        let asm = Codegen::default().generate_arm64(&compile(src)).unwrap();
+
+        assert!(asm.contains("one_func:\n    stp x29, x30, [sp, -16]!\n    mov x29, sp\n"));
+        assert!(asm.contains("main_func:\n    stp x29, x30, [sp, -16]!\n    mov x29, sp\n"));
+        assert!(asm.contains("    bl one_func\n    ldp x29, x30, [sp], 16\n    ret\n"));
+        assert!(asm.contains("main_func_end:\n    ldp x29, x30, [sp], 16\n    ret\n"));
+
+        // 모든 ret 앞에 복원 (ENTRY stub 포함)
+        assert_eq!(asm.matches("    ret\n").count(), asm.matches("    ldp x29, x30, [sp], 16\n").count());
+    }
+
+    #[test]
+    fn lib_does_not_export_main() {
+        let ir = compile("func helper(): Int { return 1; } func main(): Int { return helper(); }");
+        let main = c_symbol(ENTRY);
+
+        let asm = lib_codegen().generate_x86_64(&ir).unwrap();
+        assert!(!asm.contains(&format!("global {}\n", main)));
+        assert!(!asm.contains(&format!("\n{}:\n", main)));
+        assert!(asm.contains(&format!("global {}\n", c_symbol("helper"))));
+        assert!(asm.contains("main_func:"));
+
+        let asm = lib_codegen().generate_arm64(&ir).unwrap();
+        assert!(!asm.contains(&format!(".global {}\n", main)));
+        assert!(!asm.contains(&format!("\n{}:\n", main)));
+        assert!(asm.contains(&format!(".global {}\n", c_symbol("helper"))));
+    }
+
+    #[test]
+    fn arm64_calls_use_c_symbol_names() {
+        let asm = Codegen::default().generate_arm64(&compile(SUBSTRING_SRC)).unwrap();
+        assert!(asm.contains(&format!("    bl {}\n", c_symbol("rlk_substring"))));
+        assert!(asm.contains(&format!("    bl {}\n", c_symbol("printf"))));
+        assert!(asm.contains(&format!("{}:\n", c_symbol(ENTRY))));
+    }
+
+    #[test]
+    fn bin_keeps_entry_stub() {
+        let asm = Codegen::default().generate_arm64(&compile(SRC)).unwrap();
+        assert!(asm.contains(&format!(".global {}\n", c_symbol(ENTRY))));
+        assert!(asm.contains("bl main_func"));
+    }
+
+    const TIMING_SRC: &str = "func main(): Int { let t: Int = timeMillis(); sleep(250); return 0; }";
+
+    #[test]
+    fn x86_64_calls_time_builtins() {
+        let asm = Codegen::default().generate_x86_64(&compile(TIMING_SRC)).unwrap();
+        assert!(asm.contains(&format!("extern {}", c_symbol("rlk_time_millis"))));
+        assert!(asm.contains(&format!("call {}", c_symbol("rlk_time_millis"))));
+        assert!(asm.contains(&format!("    mov rax, 250\n    push rax\n    pop {}", arg_regs()[0])));
+        assert!(asm.contains(&format!("call {}", c_symbol("rlk_sleep"))));
+    }
+
+    #[test]
+    fn arm64_emits_statement_level_runtime_calls() {
+        let asm = Codegen::default().generate_arm64(&compile(TIMING_SRC)).unwrap();
+        assert!(asm.contains(&format!("bl {}", c_symbol("rlk_time_millis"))));
+        assert!(asm.contains(&format!(
+            "    ldr  x0, =250\n    str  x0, [sp, -16]!\n    ldr  x0, [sp], 16\n    bl {}",
+            c_symbol("rlk_sleep")
+        )));
+    }
+
+    fn generate_both(src: &str) -> [CodegenResult<String>; 2] {
+        let ir = compile(src);
+        [Codegen::default().generate_x86_64(&ir), Codegen::default().generate_arm64(&ir)]
+    }
+
+    #[test]
+    fn variable_reads_are_rejected() {
+        let src = "func main(): Int { let s: String = \"hi\"; let n: Int = 5; println(s); return 0; }";
+        for result in generate_both(src) {
+            assert_eq!(
+                result.unwrap_err().message,
+                "in `main`: reading variable `s` is not supported by codegen yet"
+            );
+        }
+    }
+
+    #[test]
+    fn runtime_arguments_must_be_lowerable() {
+        let src = "func main(): Int { let s: String = \"hi\"; println(substring(s, 0, 1)); return 0; }";
+        for result in generate_both(src) {
+            assert!(result.unwrap_err().message.contains("variable `s`"));
+        }
+
+        let src = "func main(): Int { sleep(1 + 2); return 0; }";
+        for result in generate_both(src) {
+            assert!(result.unwrap_err().message.contains("binary operator `+`"));
+        }
+    }
+
+    #[test]
+    fn user_function_calls_are_emitted() {
+        let src = "func pick(a: Int, b: Int): Int { return 2; } func main(): Int { return pick(1, 3); }";
+        let [x86, arm] = generate_both(src);
+
+        let x86 = x86.unwrap();
+        assert!(x86.contains(&format!("    pop {}\n    call pick_func", arg_regs()[0])));
+
+        let arm = arm.unwrap();
+        assert!(arm.contains("    ldr  x0, [sp], 16\n    bl pick_func"));
+    }
+
+    #[test]
+    fn when_and_if_expressions_are_rejected() {
+        let src = "func sign(x: Int): Int { return when { 1 -> 1, else -> 0 }; } func main(): Int { return sign(1); }";
+        for result in generate_both(src) {
+            assert_eq!(
+                result.unwrap_err().message,
+                "in `sign`: `when` / `if` expressions are not supported by codegen yet"
+            );
+        }
+
+        let src = "func main(): Int { println(if 1 { \"a\" } else { \"b\" }); return 0; }";
+        for result in generate_both(src) {
+            assert!(result.unwrap_err().message.contains("`when` / `if`"));
+        }
+    }
+
+    #[test]
+    fn loops_and_if_statements_are_rejected() {
+        let src = "func main(): Int { for i in 0..3 { println(\"up\"); } return 0; }";
+        for result in generate_both(src) {
+            assert_eq!(
+                result.unwrap_err().message,
+                "in `main`: `for` loops are not supported by codegen yet"
+            );
+        }
+
+        let src = "func main(): Int { if 1 { println(\"a\"); } else { } return 0; }";
+        for result in generate_both(src) {
+            assert!(result.unwrap_err().message.contains("`if` statements"));
+        }
+    }
+
+    #[test]
+    fn pure_lets_emit_nothing() {
+        let src = "func main(): Int { let n: Int = 5; let s: String = \"hi\"; let t: Int = timeMillis(); return 0; }";
+        let [x86, arm] = generate_both(src);
+
+        let x86 = x86.unwrap();
+        assert!(!x86.contains("mov rax, 5"));
+        assert!(!x86.contains("lea rax, [rel str_0]"));
+        assert!(x86.contains(&format!("call {}", c_symbol("rlk_time_millis"))));
+
+        let arm = arm.unwrap();
+        assert!(!arm.contains("ldr  x0, =5"));
+        assert!(!arm.contains("adrp x0, str_0@PAGE"));
+        assert!(arm.contains(&format!("bl {}", c_symbol("rlk_time_millis"))));
+    }
+
+    #[test]
+    fn os_selects_symbol_names_and_registers() {
+        let ir = compile(SUBSTRING_SRC);
+        let for_os = |os| Codegen { os, ..Codegen::default() };
+
+        let mac = for_os(Os::MacOs).generate_x86_64(&ir).unwrap();
+        assert!(mac.contains("global _main\n"));
+        assert!(mac.contains("call _rlk_substring\n"));
+        assert!(mac.contains("    lea rdi, [rel fmt_ln]\n"));
+
+        let windows = for_os(Os::Windows).generate_x86_64(&ir).unwrap();
+        assert!(windows.contains("call printf\n"));
+        assert!(windows.contains("    lea rcx, [rel fmt_ln]\n"));
+
+        let linux = for_os(Os::Linux).generate_arm64(&ir).unwrap();
+        assert!(linux.contains(".global main\n"));
+        assert!(linux.contains("    bl rlk_substring\n"));
+    }
+
+    #[test]
+    fn runtime_externs_only_when_used() {
+        let asm = Codegen::default().generate_x86_64(&compile(SRC)).unwrap();
+        assert!(!asm.contains("rlk_substring"));
     }
 }
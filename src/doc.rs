@@ -0,0 +1,155 @@
+use crate::parser::*;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    pub fn from_name(name: &str) -> Option<DocFormat> {
+        match name {
+            "md" | "markdown" => Some(DocFormat::Markdown),
+            "html" => Some(DocFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+// =====================================================
+// generate() → 포맷 분기
+// =====================================================
+pub fn generate(program: &Program, title: &str, format: DocFormat) -> String {
+    match format {
+        DocFormat::Markdown => generate_markdown(program, title),
+        DocFormat::Html => generate_html(program, title),
+    }
+}
+
+fn generate_markdown(program: &Program, title: &str) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "# {}", title).unwrap();
+
+    for f in &program.funcs {
+        writeln!(out).unwrap();
+        writeln!(out, "## {}", f.name).unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "```").unwrap();
+        writeln!(out, "{}", f.signature()).unwrap();
+        writeln!(out, "```").unwrap();
+
+        if !f.doc.is_empty() {
+            writeln!(out).unwrap();
+            for line in &f.doc {
+                writeln!(out, "{}", line).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+fn generate_html(program: &Program, title: &str) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "<!DOCTYPE html>").unwrap();
+    writeln!(out, "<html>").unwrap();
+    writeln!(out, "<head><meta charset=\"utf-8\"><title>{}</title></head>", escape_html(title)).unwrap();
+    writeln!(out, "<body>").unwrap();
+    writeln!(out, "<h1>{}</h1>", escape_html(title)).unwrap();
+
+    for f in &program.funcs {
+        writeln!(out, "<h2 id=\"{}\">{}</h2>", f.name, f.name).unwrap();
+        writeln!(out, "<pre><code>{}</code></pre>", escape_html(&f.signature())).unwrap();
+
+        if !f.doc.is_empty() {
+            let text: Vec<String> = f.doc.iter().map(|l| escape_html(l)).collect();
+            writeln!(out, "<p>{}</p>", text.join("\n")).unwrap();
+        }
+    }
+
+    writeln!(out, "</body>").unwrap();
+    writeln!(out, "</html>").unwrap();
+
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::parse;
+
+    const SRC: &str = "/// Adds two numbers.
+                       ///
+                       /// Returns a + b.
+                       func add(a: Int, b: Int): Int { return a + b; }
+                       func main(): Int { return add(1, 2); }";
+
+    #[test]
+    fn format_names() {
+        assert_eq!(DocFormat::from_name("md"), Some(DocFormat::Markdown));
+        assert_eq!(DocFormat::from_name("markdown"), Some(DocFormat::Markdown));
+        assert_eq!(DocFormat::from_name("html"), Some(DocFormat::Html));
+        assert_eq!(DocFormat::from_name("pdf"), None);
+    }
+
+    #[test]
+    fn markdown_layout() {
+        assert_eq!(
+            generate(&parse(SRC), "lib.rlk", DocFormat::Markdown),
+            "# lib.rlk\n\
+             \n\
+             ## add\n\
+             \n\
+             ```\n\
+             func add(a: Int, b: Int): Int\n\
+             ```\n\
+             \n\
+             Adds two numbers.\n\
+             \n\
+             Returns a + b.\n\
+             \n\
+             ## main\n\
+             \n\
+             ```\n\
+             func main(): Int\n\
+             ```\n"
+        );
+    }
+
+    #[test]
+    fn html_layout() {
+        let html = generate(&parse(SRC), "lib.rlk", DocFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>\n"));
+        assert!(html.contains("<title>lib.rlk</title>"));
+        assert!(html.contains("<h2 id=\"add\">add</h2>\n<pre><code>func add(a: Int, b: Int): Int</code></pre>\n"));
+        assert!(html.contains("<p>Adds two numbers.\n\nReturns a + b.</p>\n"));
+        assert!(html.contains("<pre><code>func main(): Int</code></pre>\n</body>"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+    }
+
+    #[test]
+    fn html_escapes_text() {
+        assert_eq!(escape_html("a < b && \"c\" > d"), "a &lt; b &amp;&amp; &quot;c&quot; &gt; d");
+
+        let html = generate(&parse("/// Returns <b> & \"c\".\nfunc f(): Int { return 0; }"), "<x>", DocFormat::Html);
+        assert!(html.contains("<h1>&lt;x&gt;</h1>"));
+        assert!(html.contains("<p>Returns &lt;b&gt; &amp; &quot;c&quot;.</p>"));
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     Func,
@@ -5,6 +6,9 @@ pub enum Token {
     Return,
     If,
     Else,
+    When,
+    For,
+    In,
 
     IntType,
     StringType,
@@ -12,6 +16,7 @@ pub enum Token {
     Ident(String),
     Number(i64),
     StringLiteral(String),
+    DocComment(String),
 
     LParen,
     RParen,
@@ -21,6 +26,8 @@ pub enum Token {
     Colon,
     Semicolon,
     Assign,
+    Arrow,
+    DotDot,
 
     Plus,
     Minus,
@@ -34,15 +41,31 @@ pub enum Token {
     EOF,
 }
 
-pub fn lex(input: &str) -> Vec<Token> {
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+pub fn lex(input: &str) -> Result<Vec<Token>, LexError> {
     use Token::*;
 
     let mut chars = input.chars().peekable();
     let mut tokens = Vec::new();
+    let mut line = 1;
+
+    let error = |line: usize, message: String| Err(LexError { line, message });
 
     while let Some(&c) = chars.peek() {
         match c {
-            ' ' | '\t' | '\r' | '\n' => { chars.next(); }
+            ' ' | '\t' | '\r' => { chars.next(); }
+            '\n' => { chars.next(); line += 1; }
 
             '(' => { chars.next(); tokens.push(LParen); }
             ')' => { chars.next(); tokens.push(RParen); }
@@ -61,9 +84,51 @@ pub fn lex(input: &str) -> Vec<Token> {
                 }
             }
             '+' => { chars.next(); tokens.push(Plus); }
-            '-' => { chars.next(); tokens.push(Minus); }
+            '-' => {
+                chars.next();
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                    tokens.push(Arrow);
+                } else {
+                    tokens.push(Minus);
+                }
+            }
             '*' => { chars.next(); tokens.push(Star); }
-            '/' => { chars.next(); tokens.push(Slash); }
+            '/' => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+
+                    // "///" 는 문서 주석, "//" 는 일반 주석
+                    let is_doc = chars.peek() == Some(&'/');
+                    if is_doc {
+                        chars.next();
+                    }
+
+                    let mut text = String::new();
+                    while let Some(&c2) = chars.peek() {
+                        if c2 == '\n' { break; }
+                        text.push(c2);
+                        chars.next();
+                    }
+
+                    if is_doc {
+                        let text = text.strip_prefix(' ').unwrap_or(&text);
+                        tokens.push(DocComment(text.trim_end().to_string()));
+                    }
+                } else {
+                    tokens.push(Slash);
+                }
+            }
+            '.' => {
+                chars.next();
+                if chars.peek() == Some(&'.') {
+                    chars.next();
+                    tokens.push(DotDot);
+                } else {
+                    return error(line, "unexpected '.'".to_string());
+                }
+            }
             '>' => { chars.next(); tokens.push(Greater); }
             '<' => { chars.next(); tokens.push(Less); }
             '!' => {
@@ -72,31 +137,41 @@ pub fn lex(input: &str) -> Vec<Token> {
                     chars.next();
                     tokens.push(NotEqual);
                 } else {
-                    panic!("Unexpected '!'");
+                    return error(line, "unexpected '!'".to_string());
                 }
             }
 
             '"' => {
                 chars.next();
+                let start_line = line;
                 let mut s = String::new();
-                while let Some(ch) = chars.next() {
-                    if ch == '"' { break; }
+                let mut closed = false;
+                for ch in chars.by_ref() {
+                    if ch == '"' { closed = true; break; }
+                    if ch == '\n' { line += 1; }
                     s.push(ch);
                 }
+                if !closed {
+                    return error(start_line, "unterminated string literal".to_string());
+                }
                 tokens.push(StringLiteral(s));
             }
 
             d if d.is_ascii_digit() => {
+                // 접두사 / '_' / 잘못된 숫자까지 한 덩어리로 읽고 검사
                 let mut num = String::new();
                 while let Some(&c2) = chars.peek() {
-                    if c2.is_ascii_digit() {
+                    if c2.is_ascii_alphanumeric() || c2 == '_' {
                         num.push(c2);
                         chars.next();
                     } else {
                         break;
                     }
                 }
-                tokens.push(Number(num.parse().unwrap()));
+                match parse_int_literal(&num) {
+                    Ok(n) => tokens.push(Number(n)),
+                    Err(message) => return error(line, message),
+                }
             }
 
             a if a.is_ascii_alphabetic() || a == '_' => {
@@ -116,16 +191,151 @@ pub fn lex(input: &str) -> Vec<Token> {
                     "return" => tokens.push(Return),
                     "if" => tokens.push(If),
                     "else" => tokens.push(Else),
+                    "when" => tokens.push(When),
+                    "for" => tokens.push(For),
+                    "in" => tokens.push(In),
                     "Int" => tokens.push(IntType),
                     "String" => tokens.push(StringType),
                     _ => tokens.push(Ident(ident)),
                 }
             }
 
-            _ => panic!("Unexpected char: {}", c),
+            _ => return error(line, format!("unexpected char {:?}", c)),
         }
     }
 
     tokens.push(EOF);
-    tokens
+    Ok(drop_stray_doc_comments(tokens))
+}
+
+// "func" 바로 앞이 아닌 "///" (함수 본문 안 등) 은 일반 주석으로 취급
+fn drop_stray_doc_comments(tokens: Vec<Token>) -> Vec<Token> {
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut before_func = false;
+
+    for tok in tokens.into_iter().rev() {
+        match tok {
+            Token::DocComment(_) if !before_func => continue,
+            Token::DocComment(_) => {}
+            Token::Func => before_func = true,
+            _ => before_func = false,
+        }
+        out.push(tok);
+    }
+
+    out.reverse();
+    out
+}
+
+// 123, 1_000_000, 0x1F, 0b1010
+fn parse_int_literal(text: &str) -> Result<i64, String> {
+    let (radix, digits, kind) = if let Some(rest) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        (16, rest, "hexadecimal")
+    } else if let Some(rest) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        (2, rest, "binary")
+    } else {
+        (10, text, "decimal")
+    };
+
+    if digits.is_empty() {
+        return Err(format!("missing digits in {} literal {}", kind, text));
+    }
+
+    if digits.starts_with('_') || digits.ends_with('_') {
+        return Err(format!("'_' must be between digits in literal {}", text));
+    }
+
+    if let Some(bad) = digits.chars().find(|c| *c != '_' && !c.is_digit(radix)) {
+        return Err(format!("invalid digit {:?} in {} literal {}", bad, kind, text));
+    }
+
+    let clean: String = digits.chars().filter(|c| *c != '_').collect();
+    i64::from_str_radix(&clean, radix)
+        .map_err(|_| format!("integer literal {} does not fit in Int (max {})", text, i64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex_number(src: &str) -> Result<i64, LexError> {
+        match lex(src)?.as_slice() {
+            [Token::Number(n), Token::EOF] => Ok(*n),
+            other => panic!("expected a single number, got {:?}", other),
+        }
+    }
+
+    fn lex_error(src: &str) -> String {
+        lex(src).unwrap_err().message
+    }
+
+    #[test]
+    fn decimal_hex_and_binary_literals() {
+        assert_eq!(lex_number("0"), Ok(0));
+        assert_eq!(lex_number("42"), Ok(42));
+        assert_eq!(lex_number("0x1F"), Ok(31));
+        assert_eq!(lex_number("0XfF"), Ok(255));
+        assert_eq!(lex_number("0b1010"), Ok(10));
+        assert_eq!(lex_number("0B0"), Ok(0));
+    }
+
+    #[test]
+    fn underscore_separated_literals() {
+        assert_eq!(lex_number("1_000_000"), Ok(1_000_000));
+        assert_eq!(lex_number("0xFF_FF"), Ok(0xFFFF));
+        assert_eq!(lex_number("0b1111_0000"), Ok(0xF0));
+        assert_eq!(lex_number("1__0"), Ok(10));
+    }
+
+    #[test]
+    fn i64_boundaries() {
+        assert_eq!(lex_number("9223372036854775807"), Ok(i64::MAX));
+        assert_eq!(lex_number("9_223_372_036_854_775_807"), Ok(i64::MAX));
+        assert_eq!(lex_number("0x7FFF_FFFF_FFFF_FFFF"), Ok(i64::MAX));
+        assert_eq!(lex_number(&format!("0b{:b}", i64::MAX)), Ok(i64::MAX));
+
+        // 음수 리터럴은 없으므로 i64::MIN 의 크기(2^63)는 범위 밖
+        assert!(lex_error("9223372036854775808").contains("does not fit in Int"));
+        assert!(lex_error("0x8000000000000000").contains("does not fit in Int"));
+        assert!(lex_error(&format!("0b1{}", "0".repeat(63))).contains("does not fit in Int"));
+        assert!(lex_error("99999999999999999999999").contains("does not fit in Int"));
+
+        // i64::MIN 은 식으로 표현
+        let tokens = lex("0 - 9223372036854775807 - 1").unwrap();
+        assert_eq!(tokens[2], Token::Number(i64::MAX));
+    }
+
+    #[test]
+    fn malformed_literals_are_errors() {
+        assert!(lex_error("0x").contains("missing digits"));
+        assert!(lex_error("0b").contains("missing digits"));
+        assert!(lex_error("1_").contains("'_'"));
+        assert!(lex_error("0x_1").contains("'_'"));
+        assert!(lex_error("0b102").contains("invalid digit '2'"));
+        assert!(lex_error("0x1G").contains("invalid digit 'G'"));
+        assert!(lex_error("12ab").contains("invalid digit 'a'"));
+    }
+
+    #[test]
+    fn errors_report_line() {
+        let err = lex("func\n\n0xZZ").unwrap_err();
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn doc_comments_only_before_func() {
+        let tokens = lex("/// a\n/// b\nfunc /// c\n{ /// d\n1 }\n/// e").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::DocComment("a".to_string()),
+                Token::DocComment("b".to_string()),
+                Token::Func,
+                Token::LBrace,
+                Token::Number(1),
+                Token::RBrace,
+                Token::EOF,
+            ]
+        );
+    }
 }
@@ -0,0 +1,11 @@
+pub mod lexer;
+pub mod parser;
+pub mod semantic;
+pub mod analysis;
+pub mod codegen;
+pub mod doc;
+pub mod printer;
+pub mod manifest;
+
+#[cfg(test)]
+mod test_util;
@@ -1,30 +1,294 @@
-mod lexer;
-mod parser;
-mod semantic;
-mod codegen;
+use rlkc::{analysis, codegen, doc, lexer, manifest, parser, printer, semantic};
 
-use std::fs;
+use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
 
 fn main() {
-    let source = fs::read_to_string("input.rlk")
-        .expect("input.rlk missing");
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("doc") => run_doc(&args[1..]),
+        Some("fmt") => run_fmt(&args[1..]),
+        Some("build") => run_build(&args[1..]),
+        _ => run_compile(&args),
+    }
+}
+
+fn parse_file(path: &str) -> parser::Program {
+    let source = fs::read_to_string(path)
+        .unwrap_or_else(|_| {
+            eprintln!("error: {} missing", path);
+            process::exit(1);
+        });
+
+    let tokens = lexer::lex(&source).unwrap_or_else(|e| {
+        eprintln!("error: {}: {}", path, e);
+        process::exit(1);
+    });
 
-    let tokens = lexer::lex(&source);
     let mut parser = parser::Parser::new(tokens);
-    let ast = parser.parse_program();
+    parser.parse_program().unwrap_or_else(|e| {
+        eprintln!("error: {}: {}", path, e);
+        process::exit(1);
+    })
+}
+
+// =====================================================
+// rlkc [FILE] [--crate-type=bin|lib] [--no-main] [--emit=asm|callgraph]
+//   → 컴파일, ASM (또는 DOT 호출 그래프) 출력
+// --no-main 은 --crate-type=lib 과 같음
+// =====================================================
+fn run_compile(args: &[String]) {
+    let mut path = "input.rlk".to_string();
+    let mut crate_type = codegen::CrateType::Bin;
+    let mut emit_callgraph = false;
+
+    for arg in args {
+        if let Some(kind) = arg.strip_prefix("--emit=") {
+            emit_callgraph = match kind {
+                "asm" => false,
+                "callgraph" => true,
+                _ => fail(format!("unknown emit kind '{}' (expected asm or callgraph)", kind)),
+            };
+        } else if arg == "--no-main" {
+            crate_type = codegen::CrateType::Lib;
+        } else if let Some(name) = arg.strip_prefix("--crate-type=") {
+            crate_type = codegen::CrateType::from_name(name).unwrap_or_else(|| {
+                eprintln!("error: unknown crate type '{}' (expected bin or lib)", name);
+                process::exit(1);
+            });
+        } else if arg.starts_with("--") {
+            eprintln!("error: unknown option '{}'", arg);
+            process::exit(1);
+        } else {
+            path = arg.clone();
+        }
+    }
+
+    let ast = parse_file(&path);
 
     let semantic = semantic::SemanticAnalyzer::new(ast);
+    if crate_type == codegen::CrateType::Bin {
+        if let Err(e) = semantic.check_entry_point() {
+            eprintln!("error: {}: {}", path, e);
+            process::exit(1);
+        }
+    }
     let ir = semantic.analyze();
 
-    // detect system architecture
-    let arch = env::consts::ARCH;   // "x86_64" or "aarch64"
+    let graph = analysis::CallGraph::build(&ir);
+    if crate_type == codegen::CrateType::Bin {
+        warn_unreachable(&graph);
+    }
 
-    let asm = if arch == "aarch64" {
-        codegen::generate_arm64(&ir)
-    } else {
-        codegen::generate_x86_64(&ir)
-    };
+    if emit_callgraph {
+        print!("{}", graph.to_dot("main"));
+        return;
+    }
+
+    // x86_64 / arm64 는 Codegen::generate 에서 자동 감지
+    let asm = codegen::Codegen { crate_type, ..Default::default() }
+        .generate(&ir)
+        .unwrap_or_else(|e| fail(format!("{}: {}", path, e)));
 
     println!("{}", asm);
 }
+
+// 실행 파일에서 main 이 절대 부르지 않는 함수
+fn warn_unreachable(graph: &analysis::CallGraph) {
+    for name in graph.unreachable_from("main") {
+        eprintln!("warning: function `{}` is never called from `main`", name);
+    }
+}
+
+// =====================================================
+// rlkc doc [FILE] [--format=md|html] → 문서 출력
+// =====================================================
+fn run_doc(args: &[String]) {
+    let mut path = "input.rlk".to_string();
+    let mut format = doc::DocFormat::Markdown;
+
+    for arg in args {
+        if let Some(name) = arg.strip_prefix("--format=") {
+            format = doc::DocFormat::from_name(name).unwrap_or_else(|| {
+                eprintln!("error: unknown doc format '{}' (expected md or html)", name);
+                process::exit(1);
+            });
+        } else {
+            path = arg.clone();
+        }
+    }
+
+    let ast = parse_file(&path);
+    print!("{}", doc::generate(&ast, &path, format));
+}
+
+// =====================================================
+// rlkc fmt [FILE] → 정규화된 소스 출력
+// =====================================================
+fn run_fmt(args: &[String]) {
+    let path = args.first().map(String::as_str).unwrap_or("input.rlk");
+    let ast = parse_file(path);
+    print!("{}", printer::print_program(&ast));
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1);
+}
+
+// =====================================================
+// rlkc build [DIR] [--target=x86_64|arm64] [--opt-level=N]
+//                  [--output=PATH] [--crate-type=bin|lib] [--no-main]
+// DIR/rlk.toml 을 읽고 CLI 옵션이 매니페스트 값을 덮어씀
+// =====================================================
+fn run_build(args: &[String]) {
+    let mut dir = PathBuf::from(".");
+    let mut target = None;
+    let mut opt_level = None;
+    let mut output = None;
+    let mut crate_type = None;
+
+    for arg in args {
+        if let Some(name) = arg.strip_prefix("--target=") {
+            target = Some(name.to_string());
+        } else if let Some(level) = arg.strip_prefix("--opt-level=") {
+            match level.parse::<u8>() {
+                Ok(n) if n <= 3 => opt_level = Some(n),
+                _ => fail(format!("opt-level must be 0..=3, got '{}'", level)),
+            }
+        } else if let Some(path) = arg.strip_prefix("--output=") {
+            output = Some(PathBuf::from(path));
+        } else if let Some(name) = arg.strip_prefix("--crate-type=") {
+            crate_type = Some(name.to_string());
+        } else if arg == "--no-main" {
+            crate_type = Some("lib".to_string());
+        } else if arg.starts_with("--") {
+            fail(format!("unknown option '{}'", arg));
+        } else {
+            dir = PathBuf::from(arg);
+        }
+    }
+
+    let m = manifest::load(&dir).unwrap_or_else(|e| fail(e.to_string()));
+
+    // CLI > rlk.toml > 기본값
+    let target = match target.or(m.target.clone()) {
+        Some(name) => codegen::Target::from_name(&name)
+            .unwrap_or_else(|| fail(format!("unknown target '{}' (expected x86_64 or arm64)", name))),
+        None => codegen::Target::host(),
+    };
+    let crate_type_name = crate_type.unwrap_or(m.crate_type.clone());
+    let crate_type = codegen::CrateType::from_name(&crate_type_name)
+        .unwrap_or_else(|| fail(format!("unknown crate type '{}' (expected bin or lib)", crate_type_name)));
+    let opt_level = opt_level.unwrap_or(m.opt_level);
+    let output = output.unwrap_or_else(|| dir.join(&m.output));
+
+    // 모든 소스 루트의 함수를 하나의 프로그램으로 합침
+    let files = collect_sources(&dir, &m.sources);
+    if files.is_empty() {
+        fail(format!("no .rlk files found in sources {:?}", m.sources));
+    }
+
+    let program = merge_sources(&files);
+
+    let semantic = semantic::SemanticAnalyzer::new(program);
+    if crate_type == codegen::CrateType::Bin {
+        if let Err(e) = semantic.check_entry_point() {
+            fail(format!("{}: {}", m.name, e));
+        }
+    }
+    let mut ir = semantic.analyze();
+
+    // opt-level >= 1: main 에서 도달할 수 없는 함수 제거 (DCE)
+    if crate_type == codegen::CrateType::Bin {
+        warn_unreachable(&analysis::CallGraph::build(&ir));
+        if opt_level >= 1 {
+            analysis::remove_unreachable(&mut ir, "main");
+        }
+    }
+
+    let asm = codegen::Codegen { crate_type, ..Default::default() }
+        .generate_for(&ir, target)
+        .unwrap_or_else(|e| fail(format!("{}: {}", m.name, e)));
+
+    let out_path = output.with_extension(target.asm_extension());
+    if let Some(parent) = out_path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|e| fail(format!("cannot create {}: {}", parent.display(), e)));
+    }
+    fs::write(&out_path, asm)
+        .unwrap_or_else(|e| fail(format!("cannot write {}: {}", out_path.display(), e)));
+
+    println!(
+        "Compiled {} ({} files, {:?}, {:?}, opt-level {}) -> {}",
+        m.name,
+        files.len(),
+        target,
+        crate_type,
+        opt_level,
+        out_path.display()
+    );
+}
+
+// 파일들의 함수를 하나의 프로그램으로, 같은 이름이 두 번 정의되면 오류
+fn merge_sources(files: &[PathBuf]) -> parser::Program {
+    let mut program = parser::Program { funcs: Vec::new() };
+    let mut defined_in: HashMap<String, &PathBuf> = HashMap::new();
+
+    for file in files {
+        for f in parse_file(&file.to_string_lossy()).funcs {
+            match defined_in.get(&f.name) {
+                Some(first) if *first == file => {
+                    fail(format!("function `{}` is defined twice in {}", f.name, file.display()))
+                }
+                Some(first) => fail(format!(
+                    "function `{}` is defined in both {} and {}",
+                    f.name,
+                    first.display(),
+                    file.display()
+                )),
+                None => {}
+            }
+            defined_in.insert(f.name.clone(), file);
+            program.funcs.push(f);
+        }
+    }
+
+    program
+}
+
+// 소스 루트 → .rlk 파일 목록 (디렉터리는 재귀, 정렬)
+fn collect_sources(dir: &Path, roots: &[String]) -> Vec<PathBuf> {
+    fn walk(path: &Path, files: &mut Vec<PathBuf>) {
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .unwrap_or_else(|e| fail(format!("cannot read {}: {}", path.display(), e)))
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+
+        for entry in entries {
+            if entry.is_dir() {
+                walk(&entry, files);
+            } else if entry.extension().is_some_and(|ext| ext == "rlk") {
+                files.push(entry);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for root in roots {
+        let path = dir.join(root);
+        if path.is_dir() {
+            walk(&path, &mut files);
+        } else if path.is_file() {
+            files.push(path);
+        } else {
+            fail(format!("source root {} does not exist", path.display()));
+        }
+    }
+    files
+}
@@ -0,0 +1,250 @@
+// rlk.toml 프로젝트 매니페스트
+//
+//   [project]
+//   name = "hello"
+//   sources = ["src"]      # .rlk 파일 또는 디렉터리
+//   output = "build/out"   # 확장자는 target 에 따라 .asm / .s
+//   target = "x86_64"      # x86_64 | arm64 (없으면 호스트)
+//   opt-level = 0          # 0..=3
+//   crate-type = "bin"     # bin | lib
+//
+// 필요한 TOML 부분집합만 지원 (문자열, 정수, 문자열 배열, [project] 섹션)
+
+use std::fs;
+use std::path::Path;
+
+pub const MANIFEST_NAME: &str = "rlk.toml";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub name: String,
+    pub sources: Vec<String>,
+    pub output: String,
+    pub target: Option<String>,
+    pub opt_level: u8,
+    pub crate_type: String,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Self {
+            name: "main".to_string(),
+            sources: vec!["src".to_string()],
+            output: "build/out".to_string(),
+            target: None,
+            opt_level: 0,
+            crate_type: "bin".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} line {}: {}", MANIFEST_NAME, self.line, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Int(i64),
+    Array(Vec<String>),
+}
+
+pub fn load(dir: &Path) -> Result<Manifest, ManifestError> {
+    let path = dir.join(MANIFEST_NAME);
+    let text = fs::read_to_string(&path).map_err(|e| ManifestError {
+        line: 0,
+        message: format!("cannot read {}: {}", path.display(), e),
+    })?;
+    parse(&text)
+}
+
+pub fn parse(text: &str) -> Result<Manifest, ManifestError> {
+    let mut manifest = Manifest::default();
+    let mut section = String::new();
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let error = |message: String| Err(ManifestError { line, message });
+
+        let content = strip_comment(raw).trim();
+        if content.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = content.strip_prefix('[') {
+            match name.strip_suffix(']') {
+                Some(name) => section = name.trim().to_string(),
+                None => return error(format!("unterminated section header {}", content)),
+            }
+            if section != "project" {
+                return error(format!("unknown section [{}]", section));
+            }
+            continue;
+        }
+
+        let Some((key, value)) = content.split_once('=') else {
+            return error(format!("expected `key = value`, got {}", content));
+        };
+        let key = key.trim();
+
+        if section != "project" {
+            return error(format!("`{}` must be inside [project]", key));
+        }
+
+        let value = match parse_value(value.trim()) {
+            Ok(v) => v,
+            Err(message) => return error(message),
+        };
+
+        match (key, value) {
+            ("name", Value::Str(s)) => manifest.name = s,
+            ("sources", Value::Array(items)) => manifest.sources = items,
+            ("output", Value::Str(s)) => manifest.output = s,
+            ("target", Value::Str(s)) => manifest.target = Some(s),
+            ("opt-level", Value::Int(n)) if (0..=3).contains(&n) => manifest.opt_level = n as u8,
+            ("opt-level", Value::Int(n)) => return error(format!("opt-level must be 0..=3, got {}", n)),
+            ("crate-type", Value::Str(s)) => manifest.crate_type = s,
+
+            ("name" | "sources" | "output" | "target" | "opt-level" | "crate-type", v) => {
+                return error(format!("wrong type for `{}`: {:?}", key, v));
+            }
+            _ => return error(format!("unknown key `{}`", key)),
+        }
+    }
+
+    Ok(manifest)
+}
+
+// 문자열 밖의 '#' 부터 줄 끝까지
+fn strip_comment(line: &str) -> &str {
+    let mut in_str = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_str = !in_str,
+            '#' if !in_str => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    if let Some(s) = parse_string(text) {
+        return Ok(Value::Str(s));
+    }
+
+    if let Some(inner) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        let mut items = Vec::new();
+        for item in split_items(inner) {
+            let item = item.trim();
+            if item.is_empty() {
+                continue; // 끝의 쉼표 허용
+            }
+            match parse_string(item) {
+                Some(s) => items.push(s),
+                None => return Err(format!("array items must be strings, got {}", item)),
+            }
+        }
+        return Ok(Value::Array(items));
+    }
+
+    text.parse()
+        .map(Value::Int)
+        .map_err(|_| format!("invalid value {}", text))
+}
+
+// 문자열 밖의 ',' 로만 나눔
+fn split_items(text: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut in_str = false;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => in_str = !in_str,
+            ',' if !in_str => {
+                items.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&text[start..]);
+    items
+}
+
+fn parse_string(text: &str) -> Option<String> {
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    if inner.contains('"') {
+        return None;
+    }
+    Some(inner.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_full_manifest() {
+        let m = parse(
+            "# project settings
+             [project]
+             name = \"hello\"   # trailing comment
+             sources = [\"src\", \"lib/extra.rlk\",]
+             output = \"out/#hello\"
+             target = \"arm64\"
+             opt-level = 2
+             crate-type = \"lib\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            m,
+            Manifest {
+                name: "hello".to_string(),
+                sources: vec!["src".to_string(), "lib/extra.rlk".to_string()],
+                output: "out/#hello".to_string(),
+                target: Some("arm64".to_string()),
+                opt_level: 2,
+                crate_type: "lib".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn array_items_may_contain_commas() {
+        let m = parse("[project]\nsources = [\"a,b\", \"c\"]").unwrap();
+        assert_eq!(m.sources, vec!["a,b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn missing_keys_use_defaults() {
+        let m = parse("[project]\nname = \"app\"\n").unwrap();
+        assert_eq!(m.name, "app");
+        assert_eq!(m.sources, vec!["src".to_string()]);
+        assert_eq!(m.output, "build/out");
+        assert_eq!(m.target, None);
+        assert_eq!(m.opt_level, 0);
+    }
+
+    #[test]
+    fn reports_errors_with_line() {
+        let err = parse("[project]\nname = \"a\"\nopt-level = 7\n").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(err.message.contains("0..=3"));
+
+        assert!(parse("[project]\ncolour = \"red\"").unwrap_err().message.contains("unknown key"));
+        assert!(parse("[deps]").unwrap_err().message.contains("unknown section"));
+        assert!(parse("name = \"a\"").unwrap_err().message.contains("[project]"));
+        assert!(parse("[project]\nsources = \"src\"").unwrap_err().message.contains("wrong type"));
+        assert!(parse("[project]\nname").unwrap_err().message.contains("key = value"));
+    }
+}
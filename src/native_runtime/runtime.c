@@ -0,0 +1,100 @@
+// Rust-like-Kotlin native runtime
+// codegen 이 만든 ASM 과 함께 링크됨 (rlk_* 심볼)
+//
+// 문자열 = NUL 로 끝나는 char*
+//   리터럴   → .data 의 str_N
+//   runtime → malloc 된 heap 문자열 (해제하지 않음)
+
+// strict -std=c11 에서도 clock_gettime / nanosleep 이 보이도록 (glibc)
+#if defined(__linux__) && !defined(_POSIX_C_SOURCE)
+#define _POSIX_C_SOURCE 200809L
+#endif
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#ifdef _WIN32
+#include <windows.h>
+#else
+#include <errno.h>
+#include <time.h>
+#endif
+
+static void rlk_panic(const char *msg) {
+    fprintf(stderr, "runtime error: %s\n", msg);
+    exit(1);
+}
+
+static char *rlk_alloc_str(size_t len) {
+    char *s = malloc(len + 1);
+    if (s == NULL) {
+        rlk_panic("out of memory");
+    }
+    s[len] = '\0';
+    return s;
+}
+
+// substring(s, start, end): String — [start, end)
+char *rlk_substring(const char *s, int64_t start, int64_t end) {
+    int64_t len = (int64_t)strlen(s);
+
+    if (start < 0 || end > len || start > end) {
+        fprintf(stderr,
+                "runtime error: substring(%lld, %lld) out of bounds for length %lld\n",
+                (long long)start, (long long)end, (long long)len);
+        exit(1);
+    }
+
+    char *out = rlk_alloc_str((size_t)(end - start));
+    memcpy(out, s + start, (size_t)(end - start));
+    return out;
+}
+
+// getenv(name): String — 설정되지 않았으면 빈 문자열
+const char *rlk_getenv(const char *name) {
+    const char *value = getenv(name);
+    return value != NULL ? value : "";
+}
+
+// timeMillis(): Int — 임의의 기준점부터의 ms (단조 증가, 구간 측정용)
+int64_t rlk_time_millis(void) {
+#ifdef _WIN32
+    return (int64_t)GetTickCount64();
+#else
+    struct timespec ts;
+    clock_gettime(CLOCK_MONOTONIC, &ts);
+    return (int64_t)ts.tv_sec * 1000 + ts.tv_nsec / 1000000;
+#endif
+}
+
+// sleep(ms): Int — 항상 0 (Unit 타입 없음)
+int64_t rlk_sleep(int64_t ms) {
+    if (ms < 0) {
+        rlk_panic("sleep: negative duration");
+    }
+
+#ifdef _WIN32
+    Sleep((DWORD)ms);
+#else
+    struct timespec ts;
+    ts.tv_sec = (time_t)(ms / 1000);
+    ts.tv_nsec = (long)(ms % 1000) * 1000000;
+
+    // 시그널로 깨어나면 남은 시간만큼 다시
+    while (nanosleep(&ts, &ts) == -1 && errno == EINTR) {
+    }
+#endif
+
+    return 0;
+}
+
+// for ... step s — 계산된 step 값 검사 (Kotlin 과 같이 0 이하는 오류)
+int64_t rlk_check_step(int64_t step) {
+    if (step <= 0) {
+        fprintf(stderr, "runtime error: for step must be positive, was %lld\n", (long long)step);
+        exit(1);
+    }
+    return step;
+}
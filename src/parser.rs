@@ -6,109 +6,204 @@ pub enum TypeName {
     String,
 }
 
-#[derive(Debug, Clone)]
+impl TypeName {
+    // 소스 코드에 쓰는 타입 이름
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TypeName::Int => "Int",
+            TypeName::String => "String",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
     Number(i64),
     StringLiteral(String),
     Var(String),
     Binary(Box<Expr>, String, Box<Expr>),
     Call(String, Vec<Expr>),
+
+    // when { cond -> expr, ..., else -> expr }
+    When(Vec<(Expr, Expr)>, Box<Expr>),
+    // if cond { expr } else { expr }
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+// for 루프 범위
+//   a..b          → a 부터 b 까지 (포함) 증가
+//   a downTo b    → a 부터 b 까지 (포함) 감소
+//   ... step s    → s 씩 (s > 0)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub start: Expr,
+    pub end: Expr,
+    pub descending: bool,
+    pub step: Option<Expr>,
 }
 
-#[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
     Let(String, TypeName, Expr),
     ExprStmt(Expr),
     Return(Expr),
     If(Expr, Vec<Stmt>, Vec<Stmt>),
+    For(String, Range, Vec<Stmt>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Function {
+    pub doc: Vec<String>,
     pub name: String,
     pub params: Vec<(String, TypeName)>,
     pub ret_type: TypeName,
     pub body: Vec<Stmt>,
 }
 
-#[derive(Debug, Clone)]
+impl Function {
+    // func add(a: Int, b: Int): Int
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|(name, t)| format!("{}: {}", name, t.as_str()))
+            .collect();
+
+        format!("func {}({}): {}", self.name, params.join(", "), self.ret_type.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Program {
     pub funcs: Vec<Function>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+type ParseResult<T> = Result<T, ParseError>;
+
+fn error<T>(message: String) -> ParseResult<T> {
+    Err(ParseError { message })
+}
+
+// 괄호 / 블록 중첩 한계 (스택 오버플로 방지)
+const MAX_DEPTH: usize = 256;
+
 pub struct Parser {
     tokens: Vec<Token>,
     pos: usize,
+    depth: usize,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, pos: 0 }
+        Self { tokens, pos: 0, depth: 0 }
     }
 
+    // 버퍼 끝을 넘어가면 항상 EOF
     fn peek(&self) -> &Token {
-        &self.tokens[self.pos]
+        self.tokens.get(self.pos).unwrap_or(&Token::EOF)
     }
 
     fn next(&mut self) -> &Token {
-        let tok = &self.tokens[self.pos];
-        self.pos += 1;
-        tok
+        match self.tokens.get(self.pos) {
+            Some(tok) => {
+                self.pos += 1;
+                tok
+            }
+            None => &Token::EOF,
+        }
     }
 
-    fn expect(&mut self, expected: &Token) {
+    fn expect(&mut self, expected: &Token) -> ParseResult<()> {
         let tok = self.next();
         if tok != expected {
-            panic!("Expected {:?}, got {:?}", expected, tok);
+            return error(format!("Expected {:?}, got {:?}", expected, tok));
         }
+        Ok(())
     }
 
-    fn expect_ident(&mut self) -> String {
+    fn expect_ident(&mut self) -> ParseResult<String> {
         match self.next() {
-            Token::Ident(name) => name.clone(),
-            other => panic!("Expected identifier, got {:?}", other),
+            Token::Ident(name) => Ok(name.clone()),
+            other => error(format!("Expected identifier, got {:?}", other)),
         }
     }
 
-    fn parse_type(&mut self) -> TypeName {
+    fn parse_type(&mut self) -> ParseResult<TypeName> {
         match self.next() {
-            Token::IntType => TypeName::Int,
-            Token::StringType => TypeName::String,
-            other => panic!("Expected type, got {:?}", other),
+            Token::IntType => Ok(TypeName::Int),
+            Token::StringType => Ok(TypeName::String),
+            other => error(format!("Expected type, got {:?}", other)),
+        }
+    }
+
+    fn enter(&mut self) -> ParseResult<()> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return error(format!("Nesting deeper than {} levels", MAX_DEPTH));
         }
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
     }
 
     // =====================================================
     // PROGRAM
     // =====================================================
-    pub fn parse_program(&mut self) -> Program {
+    pub fn parse_program(&mut self) -> ParseResult<Program> {
         let mut funcs = Vec::new();
 
         while !matches!(self.peek(), Token::EOF) {
-            funcs.push(self.parse_function());
+            let doc = self.parse_doc_comments();
+            let mut func = self.parse_function()?;
+            func.doc = doc;
+            funcs.push(func);
         }
 
-        Program { funcs }
+        Ok(Program { funcs })
+    }
+
+    // 함수 앞에 붙은 "///" 문서 주석 줄들
+    fn parse_doc_comments(&mut self) -> Vec<String> {
+        let mut doc = Vec::new();
+        while let Token::DocComment(text) = self.peek() {
+            doc.push(text.clone());
+            self.next();
+        }
+        doc
     }
 
     // =====================================================
     // FUNCTION
     // =====================================================
-    fn parse_function(&mut self) -> Function {
+    fn parse_function(&mut self) -> ParseResult<Function> {
         match self.next() {
             Token::Func => {}
-            other => panic!("Expected 'func', got {:?}", other),
+            other => return error(format!("Expected 'func', got {:?}", other)),
         }
 
-        let name = self.expect_ident();
+        let name = self.expect_ident()?;
 
-        self.expect(&Token::LParen);
+        self.expect(&Token::LParen)?;
 
         let mut params = Vec::new();
         while !matches!(self.peek(), Token::RParen) {
-            let pname = self.expect_ident();
-            self.expect(&Token::Colon);
-            let ptype = self.parse_type();
+            let pname = self.expect_ident()?;
+            self.expect(&Token::Colon)?;
+            let ptype = self.parse_type()?;
             params.push((pname, ptype));
 
             if matches!(self.peek(), Token::Comma) {
@@ -116,100 +211,134 @@ impl Parser {
             }
         }
 
-        self.expect(&Token::RParen);
-        self.expect(&Token::Colon);
-        let ret_type = self.parse_type();
-
-        self.expect(&Token::LBrace);
-
-        let mut body = Vec::new();
-        while !matches!(self.peek(), Token::RBrace) {
-            body.push(self.parse_stmt());
-        }
+        self.expect(&Token::RParen)?;
+        self.expect(&Token::Colon)?;
+        let ret_type = self.parse_type()?;
 
-        self.expect(&Token::RBrace);
+        let body = self.parse_block()?;
 
-        Function {
+        Ok(Function {
+            doc: Vec::new(),
             name,
             params,
             ret_type,
             body,
+        })
+    }
+
+    // { stmt* }
+    fn parse_block(&mut self) -> ParseResult<Vec<Stmt>> {
+        self.expect(&Token::LBrace)?;
+
+        let mut body = Vec::new();
+        while !matches!(self.peek(), Token::RBrace) {
+            body.push(self.parse_stmt()?);
         }
+
+        self.expect(&Token::RBrace)?;
+        Ok(body)
     }
 
     // =====================================================
     // STATEMENTS
     // =====================================================
-    fn parse_stmt(&mut self) -> Stmt {
-        match self.peek() {
+    fn parse_stmt(&mut self) -> ParseResult<Stmt> {
+        self.enter()?;
+        let stmt = match self.peek() {
             Token::Let => self.parse_let(),
             Token::Return => self.parse_return(),
             Token::If => self.parse_if(),
+            Token::For => self.parse_for(),
             _ => self.parse_expr_stmt(),
-        }
+        };
+        self.leave();
+        stmt
     }
 
-    fn parse_let(&mut self) -> Stmt {
+    fn parse_let(&mut self) -> ParseResult<Stmt> {
         self.next(); // let
 
-        let name = self.expect_ident();
-        self.expect(&Token::Colon);
-        let t = self.parse_type();
+        let name = self.expect_ident()?;
+        self.expect(&Token::Colon)?;
+        let t = self.parse_type()?;
 
-        self.expect(&Token::Assign);
-        let expr = self.parse_expr();
-        self.expect(&Token::Semicolon);
+        self.expect(&Token::Assign)?;
+        let expr = self.parse_expr()?;
+        self.expect(&Token::Semicolon)?;
 
-        Stmt::Let(name, t, expr)
+        Ok(Stmt::Let(name, t, expr))
     }
 
-    fn parse_return(&mut self) -> Stmt {
+    fn parse_return(&mut self) -> ParseResult<Stmt> {
         self.next(); // return
-        let expr = self.parse_expr();
-        self.expect(&Token::Semicolon);
-        Stmt::Return(expr)
+        let expr = self.parse_expr()?;
+        self.expect(&Token::Semicolon)?;
+        Ok(Stmt::Return(expr))
     }
 
-    fn parse_if(&mut self) -> Stmt {
+    fn parse_if(&mut self) -> ParseResult<Stmt> {
         self.next(); // if
 
-        let cond = self.parse_expr();
+        let cond = self.parse_expr()?;
 
         // THEN BLOCK
-        self.expect(&Token::LBrace);
-        let mut then_body = Vec::new();
-        while !matches!(self.peek(), Token::RBrace) {
-            then_body.push(self.parse_stmt());
-        }
-        self.expect(&Token::RBrace);
+        let then_body = self.parse_block()?;
 
         // ELSE BLOCK
-        self.expect(&Token::Else);
-        self.expect(&Token::LBrace);
-        let mut else_body = Vec::new();
-        while !matches!(self.peek(), Token::RBrace) {
-            else_body.push(self.parse_stmt());
-        }
-        self.expect(&Token::RBrace);
+        self.expect(&Token::Else)?;
+        let else_body = self.parse_block()?;
+
+        Ok(Stmt::If(cond, then_body, else_body))
+    }
+
+    // for i in 10 downTo 0 step 2 { ... }
+    // downTo / step 은 키워드가 아니라 문맥 식별자 (Kotlin 과 같음)
+    fn parse_for(&mut self) -> ParseResult<Stmt> {
+        self.next(); // for
+
+        let var = self.expect_ident()?;
+        self.expect(&Token::In)?;
+
+        let start = self.parse_expr()?;
+
+        let descending = match self.next() {
+            Token::DotDot => false,
+            Token::Ident(name) if name == "downTo" => true,
+            other => return error(format!("Expected '..' or 'downTo', got {:?}", other)),
+        };
+
+        let end = self.parse_expr()?;
+
+        let step = if matches!(self.peek(), Token::Ident(name) if name == "step") {
+            self.next(); // step
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
 
-        Stmt::If(cond, then_body, else_body)
+        let body = self.parse_block()?;
+
+        Ok(Stmt::For(var, Range { start, end, descending, step }, body))
     }
 
-    fn parse_expr_stmt(&mut self) -> Stmt {
-        let expr = self.parse_expr();
-        self.expect(&Token::Semicolon);
-        Stmt::ExprStmt(expr)
+    fn parse_expr_stmt(&mut self) -> ParseResult<Stmt> {
+        let expr = self.parse_expr()?;
+        self.expect(&Token::Semicolon)?;
+        Ok(Stmt::ExprStmt(expr))
     }
 
     // =====================================================
     // EXPRESSIONS
     // =====================================================
-    fn parse_expr(&mut self) -> Expr {
-        self.parse_binary()
+    fn parse_expr(&mut self) -> ParseResult<Expr> {
+        self.enter()?;
+        let expr = self.parse_binary();
+        self.leave();
+        expr
     }
 
-    fn parse_binary(&mut self) -> Expr {
-        let mut left = self.parse_primary();
+    fn parse_binary(&mut self) -> ParseResult<Expr> {
+        let mut left = self.parse_primary()?;
 
         loop {
             let op = match self.peek() {
@@ -227,21 +356,21 @@ impl Parser {
 
             self.next(); // consume operator
 
-            let right = self.parse_primary();
+            let right = self.parse_primary()?;
             left = Expr::Binary(Box::new(left), op, Box::new(right));
         }
 
-        left
+        Ok(left)
     }
 
     // =====================================================
     // PRIMARY (fixed version)
     // =====================================================
-    fn parse_primary(&mut self) -> Expr {
+    fn parse_primary(&mut self) -> ParseResult<Expr> {
         match self.next() {
-            Token::Number(n) => Expr::Number(*n),
+            Token::Number(n) => Ok(Expr::Number(*n)),
 
-            Token::StringLiteral(s) => Expr::StringLiteral(s.clone()),
+            Token::StringLiteral(s) => Ok(Expr::StringLiteral(s.clone())),
 
             Token::Ident(name) => {
                 let ident = name.clone();
@@ -251,7 +380,7 @@ impl Parser {
 
                 // 변수
                 if !is_call {
-                    return Expr::Var(ident);
+                    return Ok(Expr::Var(ident));
                 }
 
                 // 함수 호출
@@ -259,23 +388,168 @@ impl Parser {
 
                 let mut args = Vec::new();
                 while !matches!(self.peek(), Token::RParen) {
-                    args.push(self.parse_expr());
+                    args.push(self.parse_expr()?);
                     if matches!(self.peek(), Token::Comma) {
                         self.next(); // consume comma
                     }
                 }
 
-                self.expect(&Token::RParen);
-                Expr::Call(ident, args)
+                self.expect(&Token::RParen)?;
+                Ok(Expr::Call(ident, args))
             }
 
             Token::LParen => {
-                let expr = self.parse_expr();
-                self.expect(&Token::RParen);
-                expr
+                let expr = self.parse_expr()?;
+                self.expect(&Token::RParen)?;
+                Ok(expr)
             }
 
-            other => panic!("Unexpected token in primary: {:?}", other),
+            Token::When => self.parse_when(),
+
+            Token::If => self.parse_if_expr(),
+
+            other => error(format!("Unexpected token in primary: {:?}", other)),
         }
     }
+
+    // when { x > 3 -> a, x > 1 -> b, else -> c }
+    fn parse_when(&mut self) -> ParseResult<Expr> {
+        self.expect(&Token::LBrace)?;
+
+        let mut branches = Vec::new();
+        loop {
+            if matches!(self.peek(), Token::Else) {
+                self.next(); // else
+                self.expect(&Token::Arrow)?;
+                let value = self.parse_expr()?;
+                if matches!(self.peek(), Token::Comma) {
+                    self.next();
+                }
+                self.expect(&Token::RBrace)?;
+                return Ok(Expr::When(branches, Box::new(value)));
+            }
+
+            if matches!(self.peek(), Token::RBrace | Token::EOF) {
+                return error("'when' expression requires an 'else' branch".to_string());
+            }
+
+            let cond = self.parse_expr()?;
+            self.expect(&Token::Arrow)?;
+            let value = self.parse_expr()?;
+            branches.push((cond, value));
+
+            if matches!(self.peek(), Token::Comma) {
+                self.next();
+            }
+        }
+    }
+
+    // let x: Int = if a > b { a } else { b };
+    fn parse_if_expr(&mut self) -> ParseResult<Expr> {
+        let cond = self.parse_expr()?;
+
+        self.expect(&Token::LBrace)?;
+        let then_value = self.parse_expr()?;
+        self.expect(&Token::RBrace)?;
+
+        self.expect(&Token::Else)?;
+
+        self.expect(&Token::LBrace)?;
+        let else_value = self.parse_expr()?;
+        self.expect(&Token::RBrace)?;
+
+        Ok(Expr::If(Box::new(cond), Box::new(then_value), Box::new(else_value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::try_parse as parse;
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let src = "func main(): Int { let x: Int = (1 + 2); return x; }";
+
+        // 모든 접두사에 대해 패닉 없이 끝나야 함
+        for end in 0..src.len() {
+            let _ = parse(&src[..end]);
+        }
+        assert!(parse("func main(): Int { return").is_err());
+        assert!(parse(src).is_ok());
+    }
+
+    #[test]
+    fn missing_eof_token_is_an_error() {
+        let mut parser = Parser::new(vec![Token::Func, Token::Ident("main".to_string())]);
+        assert!(parser.parse_program().is_err());
+    }
+
+    #[test]
+    fn when_requires_else_branch() {
+        let err = parse("func f(x: Int): Int { return when { x > 1 -> 1 }; }").unwrap_err();
+        assert!(err.message.contains("'else'"));
+    }
+
+    #[test]
+    fn for_ranges() {
+        let program = parse(
+            "func main(): Int {
+                 for i in 0..10 { }
+                 for j in 10 downTo 0 step 2 { }
+                 return 0;
+             }",
+        )
+        .unwrap();
+
+        match &program.funcs[0].body[0] {
+            Stmt::For(var, range, _) => {
+                assert_eq!(var, "i");
+                assert!(!range.descending);
+                assert_eq!(range.step, None);
+            }
+            other => panic!("expected for, got {:?}", other),
+        }
+
+        match &program.funcs[0].body[1] {
+            Stmt::For(_, range, _) => {
+                assert_eq!(range.start, Expr::Number(10));
+                assert!(range.descending);
+                assert_eq!(range.step, Some(Expr::Number(2)));
+            }
+            other => panic!("expected for, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn doc_comments_inside_bodies_are_ignored() {
+        let program = parse(
+            "/// Entry point.
+             func main(): Int {
+                 /// not documentation
+                 let x: Int = /// nor this
+                     1;
+                 return x;
+             }",
+        )
+        .unwrap();
+        assert_eq!(program.funcs[0].doc, vec!["Entry point.".to_string()]);
+        assert_eq!(program.funcs[0].body.len(), 2);
+    }
+
+    #[test]
+    fn for_range_requires_direction() {
+        assert!(parse("func main(): Int { for i in 0 10 { } return 0; }").is_err());
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error() {
+        let src = format!(
+            "func main(): Int {{ return {}1{}; }}",
+            "(".repeat(10_000),
+            ")".repeat(10_000)
+        );
+        let err = parse(&src).unwrap_err();
+        assert!(err.message.contains("Nesting"));
+    }
 }
@@ -0,0 +1,255 @@
+use crate::parser::*;
+use std::fmt::Write;
+
+const INDENT: &str = "    ";
+
+// =====================================================
+// AST → .rlk 소스
+// parse(print(ast)) == ast 가 항상 성립해야 함
+// =====================================================
+pub fn print_program(program: &Program) -> String {
+    let mut out = String::new();
+
+    for (i, f) in program.funcs.iter().enumerate() {
+        if i > 0 {
+            writeln!(out).unwrap();
+        }
+        print_function(&mut out, f);
+    }
+
+    out
+}
+
+fn print_function(out: &mut String, f: &Function) {
+    for line in &f.doc {
+        if line.is_empty() {
+            writeln!(out, "///").unwrap();
+        } else {
+            writeln!(out, "/// {}", line).unwrap();
+        }
+    }
+
+    let params: Vec<String> = f
+        .params
+        .iter()
+        .map(|(name, t)| format!("{}: {}", name, t.as_str()))
+        .collect();
+
+    writeln!(
+        out,
+        "func {}({}): {} {{",
+        f.name,
+        params.join(", "),
+        f.ret_type.as_str()
+    )
+    .unwrap();
+
+    print_block(out, &f.body, 1);
+    writeln!(out, "}}").unwrap();
+}
+
+fn print_block(out: &mut String, body: &[Stmt], depth: usize) {
+    for stmt in body {
+        print_stmt(out, stmt, depth);
+    }
+}
+
+fn print_stmt(out: &mut String, stmt: &Stmt, depth: usize) {
+    let pad = INDENT.repeat(depth);
+
+    match stmt {
+        Stmt::Let(name, t, expr) => {
+            writeln!(out, "{}let {}: {} = {};", pad, name, t.as_str(), print_expr(expr)).unwrap();
+        }
+
+        Stmt::Return(expr) => {
+            writeln!(out, "{}return {};", pad, print_expr(expr)).unwrap();
+        }
+
+        // 문장 맨 앞의 "if" 는 if 문으로 파싱되므로 괄호로 감싼다
+        Stmt::ExprStmt(expr) if starts_with_if(expr) => {
+            writeln!(out, "{}({});", pad, print_expr(expr)).unwrap();
+        }
+
+        Stmt::ExprStmt(expr) => {
+            writeln!(out, "{}{};", pad, print_expr(expr)).unwrap();
+        }
+
+        Stmt::If(cond, then_body, else_body) => {
+            writeln!(out, "{}if {} {{", pad, print_expr(cond)).unwrap();
+            print_block(out, then_body, depth + 1);
+            writeln!(out, "{}}} else {{", pad).unwrap();
+            print_block(out, else_body, depth + 1);
+            writeln!(out, "{}}}", pad).unwrap();
+        }
+
+        Stmt::For(var, range, body) => {
+            let dir = if range.descending { " downTo " } else { ".." };
+            let step = match &range.step {
+                Some(s) => format!(" step {}", print_expr(s)),
+                None => String::new(),
+            };
+            writeln!(
+                out,
+                "{}for {} in {}{}{}{} {{",
+                pad,
+                var,
+                print_expr(&range.start),
+                dir,
+                print_expr(&range.end),
+                step
+            )
+            .unwrap();
+            print_block(out, body, depth + 1);
+            writeln!(out, "{}}}", pad).unwrap();
+        }
+    }
+}
+
+pub fn print_expr(expr: &Expr) -> String {
+    match expr {
+        Expr::Number(n) => n.to_string(),
+        Expr::StringLiteral(s) => format!("\"{}\"", s),
+        Expr::Var(name) => name.clone(),
+
+        // 파서는 우선순위 없이 왼쪽부터 묶으므로
+        // 중첩된 이항식은 항상 괄호로 감싼다
+        Expr::Binary(a, op, b) => {
+            format!("{} {} {}", print_operand(a), op, print_operand(b))
+        }
+
+        Expr::Call(name, args) => {
+            let args: Vec<String> = args.iter().map(print_expr).collect();
+            format!("{}({})", name, args.join(", "))
+        }
+
+        Expr::When(branches, else_value) => {
+            let mut arms: Vec<String> = branches
+                .iter()
+                .map(|(cond, value)| format!("{} -> {}", print_expr(cond), print_expr(value)))
+                .collect();
+            arms.push(format!("else -> {}", print_expr(else_value)));
+            format!("when {{ {} }}", arms.join(", "))
+        }
+
+        Expr::If(cond, then_value, else_value) => {
+            format!(
+                "if {} {{ {} }} else {{ {} }}",
+                print_expr(cond),
+                print_expr(then_value),
+                print_expr(else_value)
+            )
+        }
+    }
+}
+
+fn starts_with_if(expr: &Expr) -> bool {
+    match expr {
+        Expr::If(..) => true,
+        Expr::Binary(a, _, _) => starts_with_if(a),
+        _ => false,
+    }
+}
+
+fn print_operand(expr: &Expr) -> String {
+    match expr {
+        Expr::Binary(..) => format!("({})", print_expr(expr)),
+        _ => print_expr(expr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::parse;
+
+    fn assert_round_trip(src: &str) {
+        let ast = parse(src);
+        let printed = print_program(&ast);
+        assert_eq!(parse(&printed), ast, "printed source:\n{}", printed);
+
+        // 한 번 출력한 결과는 고정점이어야 함
+        assert_eq!(print_program(&parse(&printed)), printed);
+    }
+
+    #[test]
+    fn round_trips_functions_and_statements() {
+        assert_round_trip(
+            "func add(a: Int, b: Int): Int { return a + b; }
+             func main(): Int {
+                 let s: String = \"hi\";
+                 println(\"Hello from Rust-like-kotlin\");
+                 add(1, 2);
+                 return 0;
+             }",
+        );
+    }
+
+    #[test]
+    fn round_trips_nested_if() {
+        assert_round_trip(
+            "func main(): Int {
+                 if 1 > 0 {
+                     if 2 == 2 { return 1; } else { }
+                 } else {
+                     return 2;
+                 }
+                 return 0;
+             }",
+        );
+    }
+
+    #[test]
+    fn round_trips_grouped_binary_expressions() {
+        assert_round_trip(
+            "func f(x: Int): Int {
+                 return x * (x + 1) - ((2 / x) != 3);
+             }",
+        );
+    }
+
+    #[test]
+    fn round_trips_doc_comments() {
+        assert_round_trip(
+            "/// Adds two numbers.
+             ///
+             /// Returns the sum.
+             func add(a: Int, b: Int): Int { return a + b; }
+             func main(): Int { return add(1, 2); }",
+        );
+    }
+
+    #[test]
+    fn round_trips_when_and_if_expressions() {
+        assert_round_trip(
+            "func sign(x: Int): Int {
+                 let s: Int = when { x > 0 -> 1, x < 0 -> 0 - 1, else -> 0 };
+                 let m: Int = if x > 3 { x } else { 3 } + 1;
+                 (if x > 0 { 1 } else { 2 }) + 3;
+                 return when { else -> s + m };
+             }",
+        );
+    }
+
+    #[test]
+    fn round_trips_for_ranges() {
+        assert_round_trip(
+            "func main(): Int {
+                 for i in 0..10 { println(\"up\"); }
+                 for i in 10 downTo 0 step 2 {
+                     for j in i..(i + 3) step 1 { }
+                 }
+                 return 0;
+             }",
+        );
+    }
+
+    #[test]
+    fn prints_canonical_layout() {
+        let ast = parse("func main(): Int { if 1 { return 1; } else { return 0; } }");
+        assert_eq!(
+            print_program(&ast),
+            "func main(): Int {\n    if 1 {\n        return 1;\n    } else {\n        return 0;\n    }\n}\n"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::parser::*;
+use std::cell::Cell;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -10,10 +11,12 @@ pub enum IR {
     BinaryOp(Box<IRExpr>, String, Box<IRExpr>),
     CallFunc(String, Vec<IRExpr>),
     If(Box<IRExpr>, Vec<IR>, Vec<IR>),
+    While(Box<IRExpr>, Vec<IR>),
     Return(IRExpr),
 
     // ★ 출력 기능
-    Println(IRExpr),
+    Print(IRExpr),   // 개행 없음
+    Println(IRExpr), // 개행 추가
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +26,11 @@ pub enum IRExpr {
     Str(String),
     Binary(Box<IRExpr>, String, Box<IRExpr>),
     Call(String, Vec<IRExpr>),
+    // native runtime 심볼 호출 (예: "rlk_substring")
+    CallRuntime(String, Vec<IRExpr>),
+
+    // 위에서부터 처음 참인 조건의 값, 없으면 else 값
+    When(Vec<(IRExpr, IRExpr)>, Box<IRExpr>),
 }
 
 #[derive(Debug, Clone)]
@@ -38,12 +46,124 @@ pub struct IRProgram {
     pub funcs: Vec<IRFunction>,
 }
 
+// src/native_runtime/runtime.c 에 구현된 builtin
+#[derive(Debug, Clone)]
+pub struct RuntimeFunc {
+    pub symbol: String,
+    pub params: Vec<TypeName>,
+    pub ret_type: TypeName,
+}
+
+fn runtime_functions() -> HashMap<String, RuntimeFunc> {
+    let mut map = HashMap::new();
+
+    // substring(s, start, end): String — [start, end) 범위, runtime 에서 bounds 검사
+    map.insert(
+        "substring".to_string(),
+        RuntimeFunc {
+            symbol: "rlk_substring".to_string(),
+            params: vec![TypeName::String, TypeName::Int, TypeName::Int],
+            ret_type: TypeName::String,
+        },
+    );
+
+    // getenv(name): String — 없으면 ""
+    map.insert(
+        "getenv".to_string(),
+        RuntimeFunc {
+            symbol: "rlk_getenv".to_string(),
+            params: vec![TypeName::String],
+            ret_type: TypeName::String,
+        },
+    );
+
+    // timeMillis(): Int — 단조 시계 (ms), 구간 측정용
+    map.insert(
+        "timeMillis".to_string(),
+        RuntimeFunc {
+            symbol: "rlk_time_millis".to_string(),
+            params: vec![],
+            ret_type: TypeName::Int,
+        },
+    );
+
+    // sleep(ms): Int — Unit 타입이 없으므로 항상 0
+    map.insert(
+        "sleep".to_string(),
+        RuntimeFunc {
+            symbol: "rlk_sleep".to_string(),
+            params: vec![TypeName::Int],
+            ret_type: TypeName::Int,
+        },
+    );
+
+    map
+}
+
+// stmt 안의 모든 식을 (하위 식까지) 방문
+pub fn visit_stmt_exprs(stmt: &IR, f: &mut impl FnMut(&IRExpr)) {
+    match stmt {
+        IR::StoreVar(_, e) | IR::Return(e) | IR::Print(e) | IR::Println(e) => visit_expr(e, f),
+        IR::If(cond, then_body, else_body) => {
+            visit_expr(cond, f);
+            for s in then_body.iter().chain(else_body) {
+                visit_stmt_exprs(s, f);
+            }
+        }
+        IR::While(cond, body) => {
+            visit_expr(cond, f);
+            for s in body {
+                visit_stmt_exprs(s, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn visit_expr(expr: &IRExpr, f: &mut impl FnMut(&IRExpr)) {
+    f(expr);
+    match expr {
+        IRExpr::Binary(a, _, b) => {
+            visit_expr(a, f);
+            visit_expr(b, f);
+        }
+        IRExpr::Call(_, args) | IRExpr::CallRuntime(_, args) => {
+            for a in args {
+                visit_expr(a, f);
+            }
+        }
+        IRExpr::When(branches, else_value) => {
+            for (cond, value) in branches {
+                visit_expr(cond, f);
+                visit_expr(value, f);
+            }
+            visit_expr(else_value, f);
+        }
+        _ => {}
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticError {
+    pub message: String,
+}
+
+impl std::fmt::Display for SemanticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 pub struct SemanticAnalyzer {
     functions: Vec<Function>,
     map: HashMap<String, Function>,
 
     // builtin 함수 목록
     pub builtins: Vec<String>,
+    pub runtime: HashMap<String, RuntimeFunc>,
+
+    // for 루프 임시 변수 이름용 (i$end0, i$end1, ...)
+    loop_count: Cell<usize>,
 }
 
 impl SemanticAnalyzer {
@@ -56,7 +176,32 @@ impl SemanticAnalyzer {
         Self {
             functions: program.funcs,
             map,
-            builtins: vec!["println".to_string()],
+            builtins: vec!["print".to_string(), "println".to_string()],
+            runtime: runtime_functions(),
+            loop_count: Cell::new(0),
+        }
+    }
+
+    // 실행 파일 진입점: 정확히 하나의 func main(): Int
+    pub fn check_entry_point(&self) -> Result<(), SemanticError> {
+        let error = |message: String| Err(SemanticError { message });
+
+        let mains: Vec<&Function> = self.functions.iter().filter(|f| f.name == "main").collect();
+
+        match mains.as_slice() {
+            [] => error("no `main` function found; add `func main(): Int { ... }` as the program entry point".to_string()),
+
+            [main] if !main.params.is_empty() || main.ret_type != TypeName::Int => error(format!(
+                "`main` must be declared as `func main(): Int`, found `{}`",
+                main.signature()
+            )),
+
+            [_] => Ok(()),
+
+            _ => error(format!(
+                "`main` is defined {} times; a program must have exactly one entry point",
+                mains.len()
+            )),
         }
     }
 
@@ -116,22 +261,26 @@ impl SemanticAnalyzer {
             }
 
             Stmt::ExprStmt(expr) => {
-                // builtin println 변환
+                // builtin print / println 변환
                 if let Expr::Call(name, args) = expr {
                     if self.builtins.contains(name) {
                         if args.len() != 1 {
-                            panic!("println expects 1 argument");
+                            panic!("{} expects 1 argument", name);
                         }
                         let arg_t = self.expr_type(&args[0], scope);
                         if arg_t != TypeName::String {
-                            panic!("println expects String");
+                            panic!("{} expects String", name);
                         }
                         let e = self.analyze_expr(&args[0], scope);
+                        if name == "print" {
+                            return vec![IR::Print(e)];
+                        }
                         return vec![IR::Println(e)];
                     }
                 }
 
-                // 일반 표현식문은 그냥 IR 저장
+                // 일반 표현식문도 타입 검사 (when / if 분기 규칙 포함) 후 IR 저장
+                self.expr_type(expr, scope);
                 let e = self.analyze_expr(expr, scope);
                 vec![IR::StoreVar("_expr_tmp".to_string(), e)]
             }
@@ -156,9 +305,88 @@ impl SemanticAnalyzer {
 
                 vec![IR::If(Box::new(cond_ir), tvec, evec)]
             }
+
+            Stmt::For(var, range, body) => self.analyze_for(var, range, body, scope, expected_ret),
         }
     }
 
+    // for i in a..b step s { body }
+    //   →  i$endN = b; i = a; while i <= i$endN { body; i = i + s }
+    // downTo 는 >= 와 - 로 방향만 바뀜. 끝 / step 값은 한 번만,
+    // 루프 변수에 대입하기 전에 계산 (for n in 0..n 의 끝은 바깥 n)
+    // N 은 루프마다 달라서 같은 이름의 중첩 루프도 서로의 끝을 덮지 않음
+    // 루프 변수는 body 안에서만 보이고, 바깥 같은 이름 변수는 루프 뒤에 복원
+    fn analyze_for(
+        &self,
+        var: &str,
+        range: &Range,
+        body: &[Stmt],
+        scope: &mut HashMap<String, TypeName>,
+        expected_ret: &TypeName,
+    ) -> Vec<IR> {
+        for e in [&range.start, &range.end].into_iter().chain(range.step.as_ref()) {
+            if self.expr_type(e, scope) != TypeName::Int {
+                panic!("For range must be int");
+            }
+        }
+
+        let id = self.loop_count.get();
+        self.loop_count.set(id + 1);
+
+        let end_var = format!("{}$end{}", var, id);
+        let mut out = vec![IR::StoreVar(end_var.clone(), self.analyze_expr(&range.end, scope))];
+
+        let step = match &range.step {
+            None => IRExpr::Int(1),
+            Some(Expr::Number(n)) if *n <= 0 => panic!("For step must be positive, got {}", n),
+            Some(Expr::Number(n)) => IRExpr::Int(*n),
+            // 계산된 step 은 runtime 에서 양수인지 검사
+            Some(e) => {
+                let step_var = format!("{}$step{}", var, id);
+                let checked = IRExpr::CallRuntime("rlk_check_step".to_string(), vec![self.analyze_expr(e, scope)]);
+                out.push(IR::StoreVar(step_var.clone(), checked));
+                IRExpr::Var(step_var)
+            }
+        };
+
+        let start = self.analyze_expr(&range.start, scope);
+
+        let saved_var = format!("{}$outer{}", var, id);
+        let shadows = scope.contains_key(var);
+        if shadows {
+            out.push(IR::StoreVar(saved_var.clone(), IRExpr::Var(var.to_string())));
+        }
+
+        out.push(IR::StoreVar(var.to_string(), start));
+
+        let mut body_scope = scope.clone();
+        body_scope.insert(var.to_string(), TypeName::Int);
+
+        let (cmp, advance) = if range.descending { (">=", "-") } else { ("<=", "+") };
+
+        let mut body_ir = Vec::new();
+        for s in body {
+            body_ir.extend(self.analyze_stmt(s, &mut body_scope, expected_ret));
+        }
+        body_ir.push(IR::StoreVar(
+            var.to_string(),
+            IRExpr::Binary(Box::new(IRExpr::Var(var.to_string())), advance.to_string(), Box::new(step)),
+        ));
+
+        let cond = IRExpr::Binary(
+            Box::new(IRExpr::Var(var.to_string())),
+            cmp.to_string(),
+            Box::new(IRExpr::Var(end_var)),
+        );
+        out.push(IR::While(Box::new(cond), body_ir));
+
+        if shadows {
+            out.push(IR::StoreVar(var.to_string(), IRExpr::Var(saved_var)));
+        }
+
+        out
+    }
+
     fn analyze_expr(&self, expr: &Expr, scope: &HashMap<String, TypeName>) -> IRExpr {
         match expr {
             Expr::Number(n) => IRExpr::Int(*n),
@@ -173,8 +401,26 @@ impl SemanticAnalyzer {
                 )
             }
 
+            Expr::Call(name, args) if self.runtime.contains_key(name) => {
+                let func = &self.runtime[name];
+                if func.params.len() != args.len() {
+                    panic!("{} expects {} arguments", name, func.params.len());
+                }
+
+                let mut ir_args = Vec::new();
+                for (a, pt) in args.iter().zip(&func.params) {
+                    let at = self.expr_type(a, scope);
+                    if at != *pt {
+                        panic!("{} argument type mismatch: expected {:?}, got {:?}", name, pt, at);
+                    }
+                    ir_args.push(self.analyze_expr(a, scope));
+                }
+
+                IRExpr::CallRuntime(func.symbol.clone(), ir_args)
+            }
+
             Expr::Call(name, args) => {
-                // builtin println 은 이미 stmt에서 처리됨
+                // builtin print / println 은 이미 stmt에서 처리됨
                 if !self.map.contains_key(name) {
                     panic!("Unknown function {}", name);
                 }
@@ -196,6 +442,28 @@ impl SemanticAnalyzer {
 
                 IRExpr::Call(name.clone(), ir_args)
             }
+
+            Expr::When(branches, else_value) => {
+                let mut ir_branches = Vec::new();
+                for (cond, value) in branches {
+                    ir_branches.push((
+                        self.analyze_expr(cond, scope),
+                        self.analyze_expr(value, scope),
+                    ));
+                }
+                IRExpr::When(ir_branches, Box::new(self.analyze_expr(else_value, scope)))
+            }
+
+            // if 식은 분기 하나짜리 when 으로 내림
+            Expr::If(cond, then_value, else_value) => {
+                IRExpr::When(
+                    vec![(
+                        self.analyze_expr(cond, scope),
+                        self.analyze_expr(then_value, scope),
+                    )],
+                    Box::new(self.analyze_expr(else_value, scope)),
+                )
+            }
         }
     }
 
@@ -204,7 +472,10 @@ impl SemanticAnalyzer {
             Expr::Number(_) => TypeName::Int,
             Expr::StringLiteral(_) => TypeName::String,
 
-            Expr::Var(name) => scope.get(name).unwrap().clone(),
+            Expr::Var(name) => match scope.get(name) {
+                Some(t) => t.clone(),
+                None => panic!("Unknown variable {}", name),
+            },
 
             Expr::Binary(a, op, b) => {
                 let lt = self.expr_type(a, scope);
@@ -226,9 +497,329 @@ impl SemanticAnalyzer {
                     return TypeName::Int;
                 }
 
-                let func = self.map.get(name).unwrap();
+                if let Some(func) = self.runtime.get(name) {
+                    return func.ret_type.clone();
+                }
+
+                let func = match self.map.get(name) {
+                    Some(func) => func,
+                    None => panic!("Unknown function {}", name),
+                };
                 func.ret_type.clone()
             }
+
+            Expr::When(branches, else_value) => {
+                let t = self.expr_type(else_value, scope);
+                for (cond, value) in branches {
+                    if self.expr_type(cond, scope) != TypeName::Int {
+                        panic!("When condition must be int");
+                    }
+                    let vt = self.expr_type(value, scope);
+                    if vt != t {
+                        panic!("When branch type mismatch: expected {:?}, got {:?}", t, vt);
+                    }
+                }
+                t
+            }
+
+            Expr::If(cond, then_value, else_value) => {
+                if self.expr_type(cond, scope) != TypeName::Int {
+                    panic!("If condition must be int");
+                }
+                let tt = self.expr_type(then_value, scope);
+                let et = self.expr_type(else_value, scope);
+                if tt != et {
+                    panic!("If branch type mismatch: expected {:?}, got {:?}", tt, et);
+                }
+                tt
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{analyzer, compile as analyze};
+
+    fn entry_error(src: &str) -> String {
+        analyzer(src).check_entry_point().unwrap_err().message
+    }
+
+    #[test]
+    fn entry_point_accepts_single_int_main() {
+        let src = "func helper(x: Int): Int { return x; } func main(): Int { return 0; }";
+        assert_eq!(analyzer(src).check_entry_point(), Ok(()));
+    }
+
+    #[test]
+    fn entry_point_missing_main() {
+        let msg = entry_error("func helper(): Int { return 0; }");
+        assert!(msg.contains("no `main` function found"), "{}", msg);
+    }
+
+    #[test]
+    fn entry_point_wrong_signature() {
+        let msg = entry_error("func main(argc: Int): String { return \"\"; }");
+        assert!(msg.contains("found `func main(argc: Int): String`"), "{}", msg);
+    }
+
+    #[test]
+    fn entry_point_defined_twice() {
+        let msg = entry_error("func main(): Int { return 0; } func main(): Int { return 1; }");
+        assert!(msg.contains("defined 2 times"), "{}", msg);
+    }
+
+    #[test]
+    fn when_guards_lower_in_order() {
+        let ir = analyze(
+            "func f(x: Int): Int {
+                 let s: String = when { x > 3 -> \"big\", x > 1 -> \"mid\", else -> \"small\" };
+                 return 0;
+             }",
+        );
+        match &ir.funcs[0].body[0] {
+            IR::StoreVar(_, IRExpr::When(branches, else_value)) => {
+                assert_eq!(branches.len(), 2);
+                assert!(matches!(&branches[0].1, IRExpr::Str(s) if s == "big"));
+                assert!(matches!(else_value.as_ref(), IRExpr::Str(s) if s == "small"));
+            }
+            other => panic!("expected when, got {:?}", other),
         }
     }
+
+    #[test]
+    fn if_expression_lowers_to_single_branch_when() {
+        let ir = analyze("func f(x: Int): Int { let y: Int = if x > 3 { x } else { 3 }; return y; }");
+        assert!(matches!(
+            &ir.funcs[0].body[0],
+            IR::StoreVar(_, IRExpr::When(branches, _)) if branches.len() == 1
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "When branch type mismatch")]
+    fn when_branches_must_share_a_type() {
+        analyze("func f(x: Int): Int { return when { x > 3 -> 1, else -> \"no\" }; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "When condition must be int")]
+    fn when_guard_must_be_int() {
+        analyze("func f(x: Int): Int { return when { \"x\" -> 1, else -> 2 }; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "When condition must be int")]
+    fn when_statement_is_type_checked() {
+        analyze("func main(): Int { (when { \"x\" -> 1, else -> \"s\" }); return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown function nope")]
+    fn unknown_function_statement_is_reported() {
+        analyze("func main(): Int { nope(); return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "If branch type mismatch")]
+    fn if_expression_statement_is_type_checked() {
+        analyze("func main(): Int { (if 1 { 1 } else { \"s\" }); return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "If branch type mismatch")]
+    fn if_expression_branches_must_share_a_type() {
+        analyze("func f(x: Int): Int { let y: Int = if x { 1 } else { \"no\" }; return y; }");
+    }
+
+    #[test]
+    fn substring_lowers_to_runtime_call() {
+        let ir = analyze("func main(): Int { println(substring(\"hello\", 1, 3)); return 0; }");
+        match &ir.funcs[0].body[0] {
+            IR::Println(IRExpr::CallRuntime(symbol, args)) => {
+                assert_eq!(symbol, "rlk_substring");
+                assert_eq!(args.len(), 3);
+            }
+            other => panic!("expected runtime call, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn getenv_returns_string() {
+        let ir = analyze("func main(): Int { let home: String = getenv(\"HOME\"); println(home); return 0; }");
+        assert!(matches!(
+            &ir.funcs[0].body[0],
+            IR::StoreVar(_, IRExpr::CallRuntime(symbol, args)) if symbol == "rlk_getenv" && args.len() == 1
+        ));
+    }
+
+    #[test]
+    fn time_builtins_have_int_signatures() {
+        let ir = analyze(
+            "func main(): Int {
+                 let start: Int = timeMillis();
+                 sleep(10);
+                 return timeMillis() - start;
+             }",
+        );
+        let body = &ir.funcs[0].body;
+        assert!(matches!(&body[0], IR::StoreVar(_, IRExpr::CallRuntime(s, args)) if s == "rlk_time_millis" && args.is_empty()));
+        assert!(matches!(&body[1], IR::StoreVar(_, IRExpr::CallRuntime(s, args)) if s == "rlk_sleep" && args.len() == 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "sleep argument type mismatch")]
+    fn sleep_requires_int() {
+        analyze("func main(): Int { sleep(\"1s\"); return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "timeMillis expects 0 arguments")]
+    fn time_millis_takes_no_arguments() {
+        analyze("func main(): Int { return timeMillis(1); }");
+    }
+
+    #[test]
+    #[should_panic(expected = "getenv argument type mismatch")]
+    fn getenv_requires_string_name() {
+        analyze("func main(): Int { println(getenv(1)); return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "substring argument type mismatch")]
+    fn substring_checks_argument_types() {
+        analyze("func main(): Int { println(substring(\"hello\", \"1\", 3)); return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "substring expects 3 arguments")]
+    fn substring_checks_argument_count() {
+        analyze("func main(): Int { println(substring(\"hello\", 1)); return 0; }");
+    }
+
+    fn while_cond_op(ir: &IR) -> &str {
+        match ir {
+            IR::While(cond, _) => match cond.as_ref() {
+                IRExpr::Binary(_, op, _) => op,
+                other => panic!("expected comparison, got {:?}", other),
+            },
+            other => panic!("expected while, got {:?}", other),
+        }
+    }
+
+    fn while_step(ir: &IR) -> (&str, &IRExpr) {
+        match ir {
+            IR::While(_, body) => match body.last() {
+                Some(IR::StoreVar(_, IRExpr::Binary(_, op, step))) => (op, step),
+                other => panic!("expected step, got {:?}", other),
+            },
+            other => panic!("expected while, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ascending_range_counts_up_inclusive() {
+        let ir = analyze("func main(): Int { for i in 0..10 { println(\"x\"); } return 0; }");
+        let body = &ir.funcs[0].body;
+        assert!(matches!(&body[0], IR::StoreVar(v, IRExpr::Int(10)) if v == "i$end0"));
+        assert!(matches!(&body[1], IR::StoreVar(v, IRExpr::Int(0)) if v == "i"));
+        assert_eq!(while_cond_op(&body[2]), "<=");
+        assert!(matches!(while_step(&body[2]), ("+", IRExpr::Int(1))));
+    }
+
+    #[test]
+    fn down_to_with_step_counts_down() {
+        let ir = analyze("func main(): Int { for i in 10 downTo 0 step 3 { } return 0; }");
+        let body = &ir.funcs[0].body;
+        assert_eq!(while_cond_op(&body[2]), ">=");
+        assert!(matches!(while_step(&body[2]), ("-", IRExpr::Int(3))));
+    }
+
+    #[test]
+    fn computed_step_is_evaluated_once() {
+        let ir = analyze("func f(n: Int): Int { for i in 0..n step n / 2 { } return 0; }");
+        let body = &ir.funcs[0].body;
+        assert!(matches!(&body[1], IR::StoreVar(v, _) if v == "i$step0"));
+        assert!(matches!(while_step(&body[3]), ("+", IRExpr::Var(v)) if v == "i$step0"));
+    }
+
+    #[test]
+    fn computed_step_is_checked_at_runtime() {
+        let ir = analyze("func main(): Int { for i in 10 downTo 0 step 0 - 1 { } return 0; }");
+        match &ir.funcs[0].body[1] {
+            IR::StoreVar(v, IRExpr::CallRuntime(symbol, args)) => {
+                assert_eq!(v, "i$step0");
+                assert_eq!(symbol, "rlk_check_step");
+                assert!(matches!(&args[0], IRExpr::Binary(_, op, _) if op == "-"));
+            }
+            other => panic!("expected checked step, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn range_bounds_are_evaluated_before_the_loop_variable_is_assigned() {
+        let ir = analyze("func f(n: Int): Int { for n in 0..n step n { } return 0; }");
+        let body = &ir.funcs[0].body;
+        assert!(matches!(&body[0], IR::StoreVar(v, IRExpr::Var(e)) if v == "n$end0" && e == "n"));
+        assert!(matches!(
+            &body[1],
+            IR::StoreVar(v, IRExpr::CallRuntime(_, args)) if v == "n$step0" && matches!(&args[0], IRExpr::Var(e) if e == "n")
+        ));
+        // 바깥 n 을 저장한 뒤 루프 변수로 사용
+        assert!(matches!(&body[2], IR::StoreVar(v, IRExpr::Var(e)) if v == "n$outer0" && e == "n"));
+        assert!(matches!(&body[3], IR::StoreVar(v, IRExpr::Int(0)) if v == "n"));
+        assert!(matches!(&body[4], IR::While(..)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown variable i")]
+    fn loop_variable_is_not_visible_after_the_loop() {
+        analyze("func main(): Int { for i in 0..3 { } return i; }");
+    }
+
+    #[test]
+    fn loop_variable_restores_outer_binding() {
+        let ir = analyze("func main(): Int { let i: String = \"s\"; for i in 0..3 { } println(i); return 0; }");
+        let body = &ir.funcs[0].body;
+        assert!(matches!(&body[2], IR::StoreVar(v, IRExpr::Var(e)) if v == "i$outer0" && e == "i"));
+        assert!(matches!(&body[4], IR::While(..)));
+        assert!(matches!(&body[5], IR::StoreVar(v, IRExpr::Var(e)) if v == "i" && e == "i$outer0"));
+        assert!(matches!(&body[6], IR::Println(IRExpr::Var(v)) if v == "i"));
+    }
+
+    #[test]
+    fn nested_loops_with_the_same_variable_get_separate_bounds() {
+        let ir = analyze("func main(): Int { for i in 0..3 { for i in 0..5 { } } return 0; }");
+        let body = &ir.funcs[0].body;
+        assert!(matches!(&body[0], IR::StoreVar(v, IRExpr::Int(3)) if v == "i$end0"));
+
+        let IR::While(cond, inner) = &body[2] else { panic!("expected while, got {:?}", body[2]) };
+        assert!(matches!(cond.as_ref(), IRExpr::Binary(_, _, end) if matches!(end.as_ref(), IRExpr::Var(v) if v == "i$end0")));
+        assert!(matches!(&inner[0], IR::StoreVar(v, IRExpr::Int(5)) if v == "i$end1"));
+        assert!(matches!(&inner[1], IR::StoreVar(v, IRExpr::Var(e)) if v == "i$outer1" && e == "i"));
+    }
+
+    #[test]
+    fn loop_variable_is_in_scope() {
+        analyze("func main(): Int { for i in 0..3 { let j: Int = i * 2; } return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "For step must be positive")]
+    fn zero_step_is_rejected() {
+        analyze("func main(): Int { for i in 10 downTo 0 step 0 { } return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "For range must be int")]
+    fn string_range_is_rejected() {
+        analyze("func main(): Int { for i in \"a\"..3 { } return 0; }");
+    }
+
+    #[test]
+    #[should_panic(expected = "Type error")]
+    fn when_type_must_match_let_annotation() {
+        analyze("func f(x: Int): Int { let y: String = when { x -> 1, else -> 2 }; return 0; }");
+    }
 }
@@ -0,0 +1,20 @@
+// 단위 테스트 공용: 소스 → AST / IR
+use crate::lexer::lex;
+use crate::parser::*;
+use crate::semantic::*;
+
+pub fn try_parse(src: &str) -> Result<Program, ParseError> {
+    Parser::new(lex(src).unwrap()).parse_program()
+}
+
+pub fn parse(src: &str) -> Program {
+    try_parse(src).unwrap()
+}
+
+pub fn analyzer(src: &str) -> SemanticAnalyzer {
+    SemanticAnalyzer::new(parse(src))
+}
+
+pub fn compile(src: &str) -> IRProgram {
+    analyzer(src).analyze()
+}
@@ -0,0 +1,113 @@
+// rlkc build: rlk.toml 기본값 + CLI 덮어쓰기
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn project_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join("project")
+}
+
+fn out_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("rlkc-build-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn rlkc_build(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rlkc"))
+        .arg("build")
+        .arg(project_dir())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn builds_every_source_file() {
+    let out = out_dir("x86");
+    let result = rlkc_build(&["--target=x86_64", &format!("--output={}", out.join("app").display())]);
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+
+    let asm = fs::read_to_string(out.join("app.asm")).unwrap();
+    assert!(asm.contains("main_func:"));
+    assert!(asm.contains("greeting_func:"));
+    assert!(asm.contains("call greeting_func"));
+    assert!(asm.contains("call main_func"));
+
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(stdout.contains("Compiled project (2 files"), "{}", stdout);
+}
+
+#[test]
+fn cli_flags_override_manifest() {
+    let out = out_dir("lib");
+    let result = rlkc_build(&[
+        "--target=arm64",
+        "--crate-type=lib",
+        "--opt-level=2",
+        &format!("--output={}", out.join("lib").display()),
+    ]);
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+
+    let asm = fs::read_to_string(out.join("lib.s")).unwrap();
+    // C 에서 링크하는 이름: macOS 만 '_' 접두사
+    let greeting = if cfg!(target_os = "macos") { "_greeting" } else { "greeting" };
+    assert!(asm.contains(&format!(".global {}\n", greeting)));
+    assert!(!asm.contains("bl main_func"));
+
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(stdout.contains("Arm64, Lib, opt-level 2"), "{}", stdout);
+}
+
+#[test]
+fn rejects_bad_flags() {
+    let result = rlkc_build(&["--target=sparc"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("unknown target 'sparc'"));
+
+    let result = rlkc_build(&["--opt-level=9"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("opt-level must be 0..=3"));
+}
+
+#[test]
+fn missing_manifest_is_an_error() {
+    let result = Command::new(env!("CARGO_BIN_EXE_rlkc"))
+        .arg("build")
+        .arg(env::temp_dir())
+        .output()
+        .unwrap();
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("rlk.toml"));
+}
+
+#[test]
+fn duplicate_functions_across_files_are_an_error() {
+    let dir = out_dir("dup");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("rlk.toml"), "[project]\nname = \"dup\"\n").unwrap();
+    fs::write(
+        dir.join("src").join("a.rlk"),
+        "func helper(): Int { return 1; }\nfunc main(): Int { return helper(); }\n",
+    )
+    .unwrap();
+    fs::write(dir.join("src").join("b.rlk"), "func helper(): String { return \"b\"; }\n").unwrap();
+
+    let result = Command::new(env!("CARGO_BIN_EXE_rlkc"))
+        .arg("build")
+        .arg(&dir)
+        .output()
+        .unwrap();
+    assert_eq!(result.status.code(), Some(1));
+
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    let a = dir.join("src").join("a.rlk");
+    let b = dir.join("src").join("b.rlk");
+    assert!(
+        stderr.contains(&format!("function `helper` is defined in both {} and {}", a.display(), b.display())),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,184 @@
+// examples/*.rlk 골든 테스트
+//
+// 각 예제를 lex → parse → semantic → codegen 까지 돌려
+// tests/snapshots/<name>.snap 과 비교한다.
+//
+//   cargo test --test snapshots                     → 비교
+//   UPDATE_SNAPSHOTS=1 cargo test --test snapshots  → 스냅샷 갱신
+//
+// 실패한 단계 (codegen 이 아직 lowering 하지 못하는 IR 포함) 는
+// ASM 대신 == error == 섹션으로 기록된다.
+//
+// ASM 은 ARM64 / x86_64 둘 다, 호스트와 무관하게 Linux 기준
+// (C 심볼 이름 / 호출 규약) 으로 기록한다.
+
+use rlkc::codegen::{Codegen, Os};
+use rlkc::lexer;
+use rlkc::parser::Parser;
+use rlkc::printer;
+use rlkc::semantic::SemanticAnalyzer;
+
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+
+fn section(out: &mut String, name: &str, body: &str) {
+    out.push_str(&format!("== {} ==\n", name));
+    out.push_str(body);
+    if !body.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+fn panic_message(err: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = err.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = err.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+// 단계별 출력, 실패한 단계에서 error 섹션을 쓰고 멈춤
+fn run_pipeline(source: &str) -> String {
+    let mut out = String::new();
+
+    let tokens = match lexer::lex(source) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            section(&mut out, "error", &format!("lex: {}", e));
+            return out;
+        }
+    };
+
+    let ast = match Parser::new(tokens).parse_program() {
+        Ok(ast) => ast,
+        Err(e) => {
+            section(&mut out, "error", &format!("parse: {}", e));
+            return out;
+        }
+    };
+    section(&mut out, "source", &printer::print_program(&ast));
+
+    let semantic = SemanticAnalyzer::new(ast);
+    if let Err(e) = semantic.check_entry_point() {
+        section(&mut out, "error", &format!("semantic: {}", e));
+        return out;
+    }
+
+    // semantic 은 아직 panic 으로 오류를 알림
+    let ir = match panic::catch_unwind(panic::AssertUnwindSafe(|| semantic.analyze())) {
+        Ok(ir) => ir,
+        Err(e) => {
+            section(&mut out, "error", &format!("semantic: {}", panic_message(e)));
+            return out;
+        }
+    };
+    section(&mut out, "ir", &format!("{:#?}", ir.funcs));
+
+    let codegen = Codegen {
+        os: Os::Linux,
+        ..Codegen::default()
+    };
+    for (name, result) in [("arm64", codegen.generate_arm64(&ir)), ("x86_64", codegen.generate_x86_64(&ir))] {
+        match result {
+            Ok(asm) => section(&mut out, name, &asm),
+            Err(e) => {
+                section(&mut out, "error", &format!("codegen: {}", e));
+                break;
+            }
+        }
+    }
+
+    out
+}
+
+fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+fn examples() -> Vec<PathBuf> {
+    let dir = examples_dir();
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("cannot read {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "rlk"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn first_difference(expected: &str, actual: &str) -> String {
+    for (i, (e, a)) in expected.lines().zip(actual.lines()).enumerate() {
+        if e != a {
+            return format!("line {}:\n  expected: {}\n  actual:   {}", i + 1, e, a);
+        }
+    }
+    format!(
+        "line count differs: expected {}, actual {}",
+        expected.lines().count(),
+        actual.lines().count()
+    )
+}
+
+#[test]
+fn examples_match_snapshots() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let snap_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("snapshots");
+
+    let mut failures = Vec::new();
+    let paths = examples();
+    assert!(!paths.is_empty(), "no examples found");
+
+    for path in &paths {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let source = fs::read_to_string(path).unwrap();
+        let actual = run_pipeline(&source);
+        let snap_path = snap_dir.join(format!("{}.snap", name));
+
+        if update {
+            fs::create_dir_all(&snap_dir).unwrap();
+            fs::write(&snap_path, &actual).unwrap();
+            continue;
+        }
+
+        match fs::read_to_string(&snap_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{}: output differs from {}\n{}",
+                name,
+                snap_path.display(),
+                first_difference(&expected, &actual)
+            )),
+            Err(_) => failures.push(format!("{}: missing snapshot {}", name, snap_path.display())),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nrun `UPDATE_SNAPSHOTS=1 cargo test --test snapshots` to accept the new output",
+        failures.join("\n\n")
+    );
+}
+
+// 스냅샷 갱신으로 builtin 호출이 사라지지 않도록 직접 확인
+#[test]
+fn timing_example_calls_time_builtins() {
+    let source = fs::read_to_string(examples_dir().join("timing.rlk")).unwrap();
+    let out = run_pipeline(&source);
+
+    assert!(out.contains("== arm64 ==\n"), "{}", out);
+    assert!(out.contains("    bl rlk_time_millis\n"), "{}", out);
+    assert!(
+        out.contains("    ldr  x0, =100\n    str  x0, [sp, -16]!\n    ldr  x0, [sp], 16\n    bl rlk_sleep\n"),
+        "{}",
+        out
+    );
+
+    assert!(out.contains("== x86_64 ==\n"), "{}", out);
+    assert!(out.contains("    call rlk_time_millis\n"), "{}", out);
+    assert!(out.contains("    mov rax, 100\n    push rax\n    pop rdi\n"), "{}", out);
+    assert!(out.contains("    call rlk_sleep\n"), "{}", out);
+}